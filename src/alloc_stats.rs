@@ -0,0 +1,151 @@
+use std::fmt;
+
+/// What a piece of code allocated while it ran.  The counters are process-wide, so
+/// anything allocating on other threads at the same time is counted too.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: usize,
+    /// Total bytes allocated, however much of it was freed again.
+    pub bytes: usize,
+    /// The most bytes live at once, over what was live to begin with.
+    pub peak_bytes: usize,
+}
+
+impl fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} bytes, peak {} bytes",
+            self.allocations, self.bytes, self.peak_bytes
+        )
+    }
+}
+
+/// Runs `f`, counting its allocations if the `count-allocations` feature is enabled.
+/// Without the feature there is nothing to count and this returns `None`.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    #[cfg(feature = "count-allocations")]
+    {
+        let (value, stats) = counting::measure(f);
+        (value, Some(stats))
+    }
+    #[cfg(not(feature = "count-allocations"))]
+    {
+        (f(), None)
+    }
+}
+
+#[cfg(feature = "count-allocations")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::AllocStats;
+
+    static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+    static BYTES: AtomicUsize = AtomicUsize::new(0);
+    static LIVE: AtomicUsize = AtomicUsize::new(0);
+    static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+    /// The system allocator, keeping count of what passes through it.
+    struct CountingAllocator;
+
+    #[global_allocator]
+    static GLOBAL: CountingAllocator = CountingAllocator;
+
+    fn allocated(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size, Ordering::Relaxed);
+        let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(live, Ordering::Relaxed);
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                allocated(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                allocated(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                // Counted as a fresh allocation of the new size, replacing the old one.
+                LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+                allocated(new_size);
+            }
+            new_ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+        }
+    }
+
+    pub fn measure<T>(f: impl FnOnce() -> T) -> (T, AllocStats) {
+        let (allocations, bytes) = (
+            ALLOCATIONS.load(Ordering::Relaxed),
+            BYTES.load(Ordering::Relaxed),
+        );
+        let live = LIVE.load(Ordering::Relaxed);
+        PEAK.store(live, Ordering::Relaxed);
+
+        let value = f();
+        let stats = AllocStats {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+            bytes: BYTES.load(Ordering::Relaxed) - bytes,
+            peak_bytes: PEAK.load(Ordering::Relaxed).saturating_sub(live),
+        };
+        (value, stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "count-allocations")]
+    #[test]
+    fn check_measure() {
+        let (length, stats) = measure(|| {
+            let values = vec![0u8; 4096];
+            let copy = values.clone();
+            copy.len()
+        });
+        let stats = stats.unwrap();
+        assert_eq!(length, 4096);
+        assert!(stats.allocations >= 2);
+        assert!(stats.bytes >= 8192);
+        assert!(stats.peak_bytes >= 8192);
+    }
+
+    #[cfg(not(feature = "count-allocations"))]
+    #[test]
+    fn check_measure() {
+        assert_eq!(measure(|| vec![1, 2, 3].len()), (3, None));
+    }
+
+    #[test]
+    fn check_display() {
+        let stats = AllocStats {
+            allocations: 3,
+            bytes: 1024,
+            peak_bytes: 512,
+        };
+        assert_eq!(
+            stats.to_string(),
+            "3 allocations, 1024 bytes, peak 512 bytes"
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+/// The answer to a single puzzle part.
+///
+/// Advent of Code checks answers as text, so two answers are equal when they display
+/// the same way, whatever their variants: `Answer::Signed(5)` equals
+/// `Answer::Unsigned(5)`, and `Answer::Text("900")` equals `Answer::Unsigned(900)`.
+/// This is what lets an answer read from a file match the number a solution returns.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Unsigned(usize),
+    Signed(isize),
+    U128(u128),
+    Text(String),
+    /// A picture drawn by the puzzle (usually capital letters), one string per row.
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Answer::Grid(rows) if rows.len() > 1)
+    }
+
+    /// The answer on a single line, for tables.  Grids are summarised rather than drawn.
+    pub fn single_line(&self) -> String {
+        match self {
+            Answer::Grid(rows) if rows.len() > 1 => format!("<{}-row grid>", rows.len()),
+            _ => self.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Unsigned(value) => write!(f, "{}", value),
+            Answer::Signed(value) => write!(f, "{}", value),
+            Answer::U128(value) => write!(f, "{}", value),
+            Answer::Text(value) => write!(f, "{}", value),
+            Answer::Grid(rows) => {
+                for (index, row) in rows.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str("\n")?;
+                    }
+                    f.write_str(row)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Unsigned(a), Answer::Unsigned(b)) => a == b,
+            (Answer::Signed(a), Answer::Signed(b)) => a == b,
+            (Answer::U128(a), Answer::U128(b)) => a == b,
+            (Answer::Text(a), Answer::Text(b)) => a == b,
+            (Answer::Grid(a), Answer::Grid(b)) => a == b,
+            (Answer::Unsigned(a), Answer::Signed(b)) | (Answer::Signed(b), Answer::Unsigned(a)) => {
+                usize::try_from(*b) == Ok(*a)
+            }
+            (Answer::Unsigned(a), Answer::U128(b)) | (Answer::U128(b), Answer::Unsigned(a)) => {
+                *a as u128 == *b
+            }
+            (Answer::Signed(a), Answer::U128(b)) | (Answer::U128(b), Answer::Signed(a)) => {
+                u128::try_from(*a) == Ok(*b)
+            }
+            (Answer::Text(text), answer) | (answer, Answer::Text(text)) => {
+                displays_as(answer, text)
+            }
+            // Only a single-row grid can display the same as a number.
+            (Answer::Grid(rows), number) | (number, Answer::Grid(rows)) => {
+                matches!(rows.as_slice(), [row] if displays_as(number, row))
+            }
+        }
+    }
+}
+
+/// Whether `value` displays as exactly `text`, checked as it is formatted rather than by
+/// allocating a string.
+fn displays_as(value: &impl fmt::Display, text: &str) -> bool {
+    struct Matcher<'a>(&'a str);
+
+    impl fmt::Write for Matcher<'_> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.0 = self.0.strip_prefix(s).ok_or(fmt::Error)?;
+            Ok(())
+        }
+    }
+
+    let mut matcher = Matcher(text);
+    fmt::write(&mut matcher, format_args!("{}", value)).is_ok() && matcher.0.is_empty()
+}
+
+impl Eq for Answer {}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Answer {
+        Answer::Unsigned(value)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(value: isize) -> Answer {
+        Answer::Signed(value)
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(value: u128) -> Answer {
+        Answer::U128(value)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Answer {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Answer {
+        Answer::Text(value.to_string())
+    }
+}
+
+impl From<Vec<String>> for Answer {
+    fn from(rows: Vec<String>) -> Answer {
+        Answer::Grid(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+
+    #[test]
+    fn check_display() {
+        assert_eq!(Answer::from(1466usize).to_string(), "1466");
+        assert_eq!(Answer::from(-12isize).to_string(), "-12");
+        assert_eq!(Answer::from(u128::MAX).to_string(), u128::MAX.to_string());
+        assert_eq!(Answer::from("ABC").to_string(), "ABC");
+        assert_eq!(
+            Answer::from(vec!["#..".to_string(), "###".to_string()]).to_string(),
+            "#..\n###"
+        );
+    }
+
+    #[test]
+    fn check_equality_within_variants() {
+        assert_eq!(Answer::from(900usize), Answer::from(900usize));
+        assert_ne!(Answer::from(-900isize), Answer::from(900isize));
+        assert_ne!(Answer::from("ABC"), Answer::from("ABD"));
+        let grid = |rows: &[&str]| Answer::from(rows.iter().map(|r| r.to_string()).collect_vec());
+        assert_eq!(grid(&["#..", "###"]), grid(&["#..", "###"]));
+        assert_ne!(grid(&["#..", "###"]), grid(&["#..", "##."]));
+    }
+
+    #[test]
+    fn check_equality_across_variants() {
+        assert_eq!(Answer::from(900isize), Answer::from(900usize));
+        assert_eq!(Answer::from(900u128), Answer::from(900isize));
+        assert_eq!(Answer::from(900u128), Answer::from("900"));
+        assert_eq!(Answer::from("7"), Answer::from(7usize));
+        assert_ne!(Answer::from(-900isize), Answer::from(900usize));
+        assert_ne!(Answer::from(-900isize), Answer::from(u128::MAX));
+        assert_ne!(Answer::from("0900"), Answer::from(900usize));
+        assert_ne!(Answer::from("90"), Answer::from(900usize));
+        assert_ne!(Answer::from("9000"), Answer::from(900usize));
+
+        let grid = |rows: &[&str]| Answer::from(rows.iter().map(|r| r.to_string()).collect_vec());
+        assert_eq!(grid(&["#..", "###"]), Answer::from("#..\n###"));
+        assert_eq!(grid(&["42"]), Answer::from(42usize));
+        assert_ne!(grid(&["4", "2"]), Answer::from(42usize));
+    }
+}
@@ -0,0 +1,148 @@
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use serde::{Serialize, Serializer};
+
+use crate::input::Input;
+use crate::solution::Registration;
+
+/// Summary statistics over repeated timings of the same step.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Stats {
+    #[serde(rename = "min_us", serialize_with = "as_micros")]
+    pub min: Duration,
+    #[serde(rename = "median_us", serialize_with = "as_micros")]
+    pub median: Duration,
+    #[serde(rename = "mean_us", serialize_with = "as_micros")]
+    pub mean: Duration,
+    #[serde(rename = "stddev_us", serialize_with = "as_micros")]
+    pub stddev: Duration,
+}
+
+fn as_micros<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(duration.as_secs_f64() * 1e6)
+}
+
+impl Stats {
+    /// Panics if there are no samples.
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let count = sorted.len();
+        let middle = count / 2;
+        let median = if count.is_multiple_of(2) {
+            (sorted[middle - 1] + sorted[middle]) / 2
+        } else {
+            sorted[middle]
+        };
+
+        let secs = sorted.iter().map(Duration::as_secs_f64).collect::<Vec<_>>();
+        let mean = secs.iter().sum::<f64>() / count as f64;
+        let variance = secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / count as f64;
+
+        Stats {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PartBench {
+    pub puzzle: usize,
+    /// `None` if the part has no solution yet.
+    pub answer: Option<String>,
+    pub solve: Stats,
+}
+
+/// Timings for one day, with parsing and each part's solve measured separately.  Reading
+/// the input file is not included.
+#[derive(Debug, Serialize)]
+pub struct BenchReport {
+    pub year: usize,
+    pub day: usize,
+    pub warmup: usize,
+    pub runs: usize,
+    pub parse: Stats,
+    pub parts: Vec<PartBench>,
+}
+
+/// Runs `puzzles` against `input` `warmup` times untimed, then `runs` times timed.
+/// Fails on the first error from the solution rather than timing a failure.
+pub fn bench_day(
+    registration: &Registration,
+    puzzles: &[usize],
+    input: &Input,
+    warmup: usize,
+    runs: usize,
+) -> Result<BenchReport> {
+    if runs == 0 {
+        return Err(anyhow!("Need at least one timed run"));
+    }
+    for _ in 0..warmup {
+        registration.run_parts(puzzles, input)?;
+    }
+
+    let mut parse_samples = Vec::with_capacity(runs);
+    let mut solve_samples = vec![Vec::with_capacity(runs); puzzles.len()];
+    let mut answers = vec![None; puzzles.len()];
+    for _ in 0..runs {
+        let day_run = registration.run_parts(puzzles, input)?;
+        parse_samples.push(day_run.parse_duration);
+        for (index, part) in day_run.parts.into_iter().enumerate() {
+            solve_samples[index].push(part.duration);
+            answers[index] = part.answer?.map(|answer| answer.to_string());
+        }
+    }
+
+    Ok(BenchReport {
+        year: registration.year,
+        day: registration.day,
+        warmup,
+        runs,
+        parse: Stats::from_samples(&parse_samples),
+        parts: puzzles
+            .iter()
+            .zip(answers)
+            .zip(&solve_samples)
+            .map(|((&puzzle, answer), samples)| PartBench {
+                puzzle,
+                answer,
+                solve: Stats::from_samples(samples),
+            })
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::find_solution;
+
+    #[test]
+    fn check_stats() {
+        let samples = [4, 1, 3, 2].map(Duration::from_micros);
+        let stats = Stats::from_samples(&samples);
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.median, Duration::from_nanos(2500));
+        assert_eq!(stats.mean, Duration::from_nanos(2500));
+        // Population standard deviation of 1, 2, 3, 4 is sqrt(1.25).
+        assert_eq!(stats.stddev.as_nanos(), 1118);
+    }
+
+    #[test]
+    fn check_bench_day() {
+        let input = Input::from("1\n3\n2\n4\n5\n");
+        let report = bench_day(find_solution(2021, 1).unwrap(), &[1, 2], &input, 1, 3).unwrap();
+        assert_eq!(report.runs, 3);
+        assert_eq!(report.parts.len(), 2);
+        assert_eq!(report.parts[0].answer.as_deref(), Some("3"));
+        assert_eq!(report.parts[1].answer.as_deref(), Some("2"));
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["parts"][1]["puzzle"], 2);
+        assert!(json["parse"]["median_us"].is_f64());
+    }
+}
@@ -0,0 +1,143 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::solution::{Progress, Registration};
+
+/// Puzzles unlock at midnight US Eastern Standard Time, UTC-5.
+const UNLOCK_UTC_OFFSET_SECS: i64 = -5 * 60 * 60;
+
+/// Where the current time comes from, so that date-dependent behaviour can be tested.
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// A clock stopped at a given time.
+pub struct FixedClock(pub SystemTime);
+
+impl Clock for FixedClock {
+    fn now(&self) -> SystemTime {
+        self.0
+    }
+}
+
+/// The date in the puzzles' time zone, as (year, month, day).
+pub fn puzzle_date(clock: &impl Clock) -> (usize, usize, usize) {
+    let secs = match clock.now().duration_since(UNIX_EPOCH) {
+        Ok(since) => since.as_secs() as i64,
+        Err(before) => -(before.duration().as_secs() as i64),
+    };
+    civil_from_days((secs + UNLOCK_UTC_OFFSET_SECS).div_euclid(24 * 60 * 60))
+}
+
+/// Converts days since 1970-01-01 to a (year, month, day) date in the proleptic
+/// Gregorian calendar.  This is Howard Hinnant's `civil_from_days`.
+fn civil_from_days(days: i64) -> (usize, usize, usize) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year as usize, month as usize, day as usize)
+}
+
+/// Fills in whichever of the year and day weren't given.  During Advent (December 1-25
+/// in the puzzles' time zone) that's today's puzzle.  Otherwise it's the latest solved
+/// day of the latest year with one (or of the given year) in `registrations`, where a
+/// day counts as solved once either part is.  Stubbed days are passed over.
+pub fn default_year_and_day<'a>(
+    clock: &impl Clock,
+    registrations: impl IntoIterator<Item = &'a Registration>,
+    year: Option<usize>,
+    day: Option<usize>,
+) -> (usize, usize) {
+    let today = match puzzle_date(clock) {
+        (year, 12, day) if day <= 25 => Some((year, day)),
+        _ => None,
+    };
+    let solved = registrations
+        .into_iter()
+        .filter(|r| r.progress.contains(&Progress::Solved))
+        .map(|r| (r.year, r.day))
+        .collect::<Vec<_>>();
+    let latest_year = solved.iter().map(|&(year, _)| year).max().unwrap_or(2015);
+    let year = year.or(today.map(|(year, _)| year)).unwrap_or(latest_year);
+    let day = day
+        .or(today
+            .filter(|&(today_year, _)| today_year == year)
+            .map(|(_, day)| day))
+        .or_else(|| {
+            solved
+                .iter()
+                .filter(|&&(solved_year, _)| solved_year == year)
+                .map(|&(_, day)| day)
+                .max()
+        })
+        .unwrap_or(1);
+    (year, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::REGISTRY;
+    use std::time::Duration;
+
+    fn at(unix_secs: u64) -> FixedClock {
+        FixedClock(UNIX_EPOCH + Duration::from_secs(unix_secs))
+    }
+
+    #[test]
+    fn check_puzzle_date() {
+        assert_eq!(puzzle_date(&at(0)), (1969, 12, 31));
+        // 2023-12-01T04:59:59Z is still 30 November in UTC-5; a second later it's the 1st.
+        assert_eq!(puzzle_date(&at(1_701_406_799)), (2023, 11, 30));
+        assert_eq!(puzzle_date(&at(1_701_406_800)), (2023, 12, 1));
+        // 2024-02-29T12:00:00Z
+        assert_eq!(puzzle_date(&at(1_709_208_000)), (2024, 2, 29));
+    }
+
+    #[test]
+    fn check_defaults_during_advent() {
+        // 2023-12-05T05:00:00Z, when day 5 unlocks.
+        let clock = at(1_701_752_400);
+        let defaults = |year, day| default_year_and_day(&clock, REGISTRY, year, day);
+        assert_eq!(defaults(None, None), (2023, 5));
+        assert_eq!(defaults(None, Some(1)), (2023, 1));
+        assert_eq!(defaults(Some(2021), None), (2021, 3));
+    }
+
+    #[test]
+    fn check_defaults_outside_advent() {
+        // 2024-02-29T12:00:00Z
+        let clock = at(1_709_208_000);
+        let defaults = |year, day| default_year_and_day(&clock, REGISTRY, year, day);
+        // 2023 only has a stub, and 2022's days 3 and 4 are stubs too.
+        assert_eq!(defaults(None, None), (2022, 2));
+        assert_eq!(defaults(Some(2022), None), (2022, 2));
+        assert_eq!(defaults(Some(2023), None), (2023, 1));
+        assert_eq!(defaults(Some(2022), Some(2)), (2022, 2));
+        assert_eq!(defaults(Some(2019), None), (2019, 1));
+        // Boxing Day is after the last puzzle.
+        assert_eq!(
+            default_year_and_day(&at(1_703_592_000), REGISTRY, None, None),
+            (2022, 2)
+        );
+        assert_eq!(default_year_and_day(&clock, [], None, None), (2015, 1));
+    }
+}
@@ -0,0 +1,282 @@
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+
+#[derive(Debug, Parser)]
+#[command(name = "aoc", version, author, about = "Advent of code")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+    /// Show debug messages from solutions, or with -vv trace messages too.
+    #[arg(short, long, global = true, action = ArgAction::Count)]
+    pub verbose: u8,
+    /// Log levels per module, such as `aoc::old::coordinates=trace,aoc::y2021=debug`.
+    #[arg(long, global = true, value_name = "FILTER", env = "AOC_LOG")]
+    pub log: Option<String>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Run one day's puzzles, or every registered day.
+    Run(RunArgs),
+    /// Time one day's puzzles over many runs, reporting parse and solve separately.
+    Bench(BenchArgs),
+    /// Flag parses and parts whose latest benchmark is slower than the previous or best
+    /// one.  Only `aoc bench` records benchmarks; the timings `aoc run` prints are not
+    /// kept.
+    Compare(CompareArgs),
+    /// Check solutions against the known answers in answers.toml.
+    Verify(VerifyArgs),
+    /// Set up a new day's solution module, registration, and example and answer files.
+    New(NewArgs),
+    /// Write example inputs and answers from a saved puzzle page.
+    Extract(ExtractArgs),
+    /// Download a day's puzzle input, unless it has already been downloaded.
+    Fetch(FetchArgs),
+    /// Run one part and submit its answer, keeping a history of guesses.
+    Submit(SubmitArgs),
+    /// Show which parts are solved, verified, stubbed or no longer available.
+    List(ListArgs),
+    /// Re-run a day's examples, then its input, whenever they or the source change.
+    Watch(WatchArgs),
+    /// Print a completion script for your shell.
+    Completions { shell: Shell },
+}
+
+/// Which puzzle to run, and on what input.
+#[derive(Debug, Args)]
+pub struct PuzzleArgs {
+    /// Which Advent of Code year?  Defaults to this year during Advent, otherwise the
+    /// latest year with a solved day.
+    #[arg(short, long, value_parser = parse_year)]
+    pub year: Option<usize>,
+    /// Input file, directory of input files, or - for standard input.
+    #[arg(short, long, value_name = "PATH")]
+    pub input: Option<String>,
+    /// Which day?  Defaults to today during Advent, otherwise the latest solved day.
+    #[arg(value_parser = parse_day)]
+    pub day: Option<usize>,
+    /// Which puzzle? Both if omitted.
+    #[arg(value_parser = parse_part)]
+    pub puzzle: Option<usize>,
+}
+
+impl PuzzleArgs {
+    pub fn puzzles(&self) -> Vec<usize> {
+        match self.puzzle {
+            Some(puzzle) => vec![puzzle],
+            None => vec![1, 2],
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct RunArgs {
+    #[command(flatten)]
+    pub puzzle: PuzzleArgs,
+    /// Run every registered day of YEAR and print a summary table.
+    #[arg(long, requires = "year", conflicts_with = "day")]
+    pub all: bool,
+    /// Run every registered day of every year and print a summary table.
+    #[arg(long, conflicts_with_all = ["year", "day", "all"])]
+    pub all_years: bool,
+    /// Run the day against every input file in DIR, checking each against the answers
+    /// in a NAME.answer file alongside it, if there is one.
+    #[arg(long, value_name = "DIR", conflicts_with_all = ["input", "all", "all_years"])]
+    pub inputs_dir: Option<PathBuf>,
+    /// How to print the results.
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+    /// Give up on a part that takes longer than this (the first part's time includes the
+    /// parse), or 0 to wait forever.
+    #[arg(long, value_name = "SECONDS", default_value_t = 60)]
+    pub timeout: u64,
+    /// Run this many parts at once with --all or --all-years.  Timings are most
+    /// accurate with one.
+    #[arg(short, long, value_name = "N", default_value = "1")]
+    pub jobs: NonZeroUsize,
+}
+
+impl RunArgs {
+    pub fn timeout(&self) -> Option<Duration> {
+        (self.timeout > 0).then(|| Duration::from_secs(self.timeout))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Readable text.
+    Text,
+    /// One JSON object per part, one per line.
+    Json,
+}
+
+#[derive(Debug, Args)]
+pub struct BenchArgs {
+    #[command(flatten)]
+    pub puzzle: PuzzleArgs,
+    /// Number of timed runs.
+    #[arg(short = 'n', long, value_name = "N", default_value_t = 100)]
+    pub runs: usize,
+    /// Number of untimed runs before timing starts.
+    #[arg(long, value_name = "N", default_value_t = 10)]
+    pub warmup: usize,
+    /// Where to write the results as JSON.
+    #[arg(short, long, value_name = "FILE", default_value = "bench_output.txt")]
+    pub output: PathBuf,
+    /// History file to record the results in, keyed by git commit.
+    #[arg(long, value_name = "FILE", default_value = "bench_history.jsonl")]
+    pub history: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub struct CompareArgs {
+    /// History file written by `aoc bench`.
+    #[arg(long, value_name = "FILE", default_value = "bench_history.jsonl")]
+    pub history: PathBuf,
+    /// How much slower a part must get to be flagged.
+    #[arg(short, long, value_name = "PERCENT", default_value_t = 10.0)]
+    pub threshold: f64,
+}
+
+#[derive(Debug, Args)]
+pub struct VerifyArgs {
+    /// Only verify this year.
+    #[arg(short, long, value_parser = parse_year)]
+    pub year: Option<usize>,
+    /// Known-answers file to check against, instead of answers.toml.
+    #[arg(long, value_name = "FILE")]
+    pub answers: Option<PathBuf>,
+    /// Only verify this day.
+    #[arg(value_parser = parse_day)]
+    pub day: Option<usize>,
+}
+
+/// A single day, which must be given.
+#[derive(Debug, Args)]
+pub struct DayArgs {
+    /// Which Advent of Code year?
+    #[arg(value_parser = parse_year)]
+    pub year: usize,
+    /// Which day?
+    #[arg(value_parser = parse_day)]
+    pub day: usize,
+}
+
+#[derive(Debug, Args)]
+pub struct NewArgs {
+    #[command(flatten)]
+    pub day: DayArgs,
+    /// Switch to (creating if needed) the YEAR-DAY git branch first.
+    #[arg(short, long)]
+    pub branch: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ExtractArgs {
+    #[command(flatten)]
+    pub day: DayArgs,
+    /// The puzzle page, saved as HTML.
+    pub page: PathBuf,
+    /// Only list the code blocks and highlighted answers.
+    #[arg(short, long)]
+    pub list: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct FetchArgs {
+    #[command(flatten)]
+    pub day: DayArgs,
+    /// Site to download from, instead of the configured one.
+    #[arg(long, value_name = "URL")]
+    pub base_url: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct SubmitArgs {
+    #[command(flatten)]
+    pub day: DayArgs,
+    /// Which puzzle?
+    #[arg(value_parser = parse_part)]
+    pub part: usize,
+    /// Site to submit to, instead of the configured one.
+    #[arg(long, value_name = "URL")]
+    pub base_url: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct ListArgs {
+    /// Only this Advent of Code year, instead of every year with solutions.
+    #[arg(short, long, value_parser = parse_year)]
+    pub year: Option<usize>,
+    /// Print a Markdown table of the registered days instead of calendars.
+    #[arg(long)]
+    pub markdown: bool,
+    /// History file to take the last benchmark times from.
+    #[arg(long, value_name = "FILE", default_value = "bench_history.jsonl")]
+    pub history: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub struct WatchArgs {
+    #[command(flatten)]
+    pub day: DayArgs,
+    /// Input file, instead of the one in the configured input directory.
+    #[arg(short, long, value_name = "FILE")]
+    pub input: Option<PathBuf>,
+    /// How often to check for changes, in milliseconds.
+    #[arg(long, value_name = "MS", default_value_t = 500)]
+    pub interval: u64,
+}
+
+fn parse_number(value: &str) -> Result<usize, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{:?} is not a number", value))
+}
+
+fn parse_year(value: &str) -> Result<usize, String> {
+    match parse_number(value)? {
+        year if year >= 2015 => Ok(year),
+        _ => Err("Advent of Code started in 2015".to_string()),
+    }
+}
+
+fn parse_day(value: &str) -> Result<usize, String> {
+    match parse_number(value)? {
+        day @ 1..=25 => Ok(day),
+        _ => Err("days run from 1 to 25".to_string()),
+    }
+}
+
+fn parse_part(value: &str) -> Result<usize, String> {
+    match parse_number(value)? {
+        part @ 1..=2 => Ok(part),
+        _ => Err("each day has puzzles 1 and 2".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn check_cli_is_consistent() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn check_validation() {
+        assert!(Cli::try_parse_from(["aoc", "run", "8", "2", "-y", "2021"]).is_ok());
+        assert!(Cli::try_parse_from(["aoc", "run", "26", "-y", "2021"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "run", "8", "3", "-y", "2021"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "run", "8", "-y", "2014"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "run", "--all"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "run", "--all-years", "-y", "2021"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "submit", "2021", "0", "1"]).is_err());
+    }
+}
@@ -0,0 +1,200 @@
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::config::Config;
+
+/// Identifies this tool to the Advent of Code servers, as their owner asks.
+const USER_AGENT: &str = concat!(
+    "aoc/",
+    env!("CARGO_PKG_VERSION"),
+    " (",
+    env!("CARGO_PKG_AUTHORS"),
+    ")"
+);
+
+/// Talks to the Advent of Code site (or a stand-in for it at another base URL) as the
+/// user whose session token is configured.
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Client {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Result<Client> {
+        let session = config.session.as_deref().ok_or_else(|| {
+            anyhow!("No session token: set AOC_SESSION or add `session` to the config file")
+        })?;
+        Ok(Client::new(config.base_url(), session))
+    }
+
+    /// Downloads the user's puzzle input.
+    pub fn input(&self, year: usize, day: usize) -> Result<String> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call();
+        let response = match response {
+            Ok(response) => response,
+            Err(ureq::Error::Status(status, response)) => {
+                return Err(status_error("Fetching", &url, status, response))
+            }
+            Err(err) => return Err(err).with_context(|| format!("Failed to fetch {}", url)),
+        };
+        let mut text = String::new();
+        response
+            .into_reader()
+            .read_to_string(&mut text)
+            .with_context(|| format!("Failed to read the response from {}", url))?;
+        Ok(text)
+    }
+
+    /// Submits an answer, returning the page the site responds with.
+    pub fn submit(&self, year: usize, day: usize, part: usize, answer: &str) -> Result<String> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+        match response {
+            Ok(response) => response
+                .into_string()
+                .with_context(|| format!("Failed to read the response from {}", url)),
+            Err(ureq::Error::Status(status, response)) => {
+                Err(status_error("Submitting to", &url, status, response))
+            }
+            Err(err) => Err(err).with_context(|| format!("Failed to submit to {}", url)),
+        }
+    }
+}
+
+fn status_error(action: &str, url: &str, status: u16, response: ureq::Response) -> anyhow::Error {
+    let body = response.into_string().unwrap_or_default();
+    let reason = match status {
+        400 | 401 | 403 => "the session token has probably expired",
+        404 => "the puzzle probably hasn't unlocked yet",
+        _ => body.trim(),
+    };
+    anyhow!("{} {} failed with {}: {}", action, url, status, reason)
+}
+
+/// Downloads the input for the day to `input_path`, unless it is already there.
+/// Returns whether it downloaded anything.
+pub fn fetch_input(client: &Client, year: usize, day: usize, input_path: &Path) -> Result<bool> {
+    if input_path.exists() {
+        return Ok(false);
+    }
+    let text = client.input(year, day)?;
+    if let Some(dir) = input_path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    // Write to a temporary name first, so an interrupted download can't leave a partial
+    // input that would never be fetched again.
+    let partial_path = input_path.with_extension("partial");
+    fs::write(&partial_path, text)
+        .with_context(|| format!("Failed to write {}", partial_path.display()))?;
+    fs::rename(&partial_path, input_path)
+        .with_context(|| format!("Failed to write {}", input_path.display()))?;
+    Ok(true)
+}
+
+/// A stand-in HTTP server for testing the client.
+#[cfg(test)]
+pub(crate) mod test_server {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+
+    /// Serves one request with `status` and `body`, returning the base URL and a handle
+    /// that yields the raw request (head and body).
+    pub fn serve_once(status: u16, body: &'static str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = length.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+            }
+            let mut body_bytes = vec![0; content_length];
+            reader.read_exact(&mut body_bytes).unwrap();
+            request.push_str(&String::from_utf8(body_bytes).unwrap());
+
+            let response = format!(
+                "HTTP/1.1 {} Test\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            request
+        });
+        (base_url, handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_server::serve_once;
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn check_fetch_input() {
+        let (base_url, server) = serve_once(200, "1\n2\n3\n");
+        let client = Client::new(&base_url, "secret\n");
+        let dir = TempDir::new("fetch");
+        let input_path = dir.join("inputs").join("input-2021-day01");
+
+        assert!(fetch_input(&client, 2021, 1, &input_path).unwrap());
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2021/day/1/input HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=secret\r\n"));
+        assert!(request.contains(&format!("User-Agent: {}\r\n", USER_AGENT)));
+        assert_eq!(fs::read_to_string(&input_path).unwrap(), "1\n2\n3\n");
+
+        // There's no server any more, so this only succeeds if it doesn't download again.
+        assert!(!fetch_input(&client, 2021, 1, &input_path).unwrap());
+    }
+
+    #[test]
+    fn check_fetch_failure_leaves_no_file() {
+        let (base_url, server) = serve_once(400, "Puzzle inputs differ by user.");
+        let client = Client::new(&base_url, "expired");
+        let dir = TempDir::new("fetch-failure");
+        let input_path = dir.join("input-2021-day01");
+
+        let err = fetch_input(&client, 2021, 1, &input_path).unwrap_err();
+        server.join().unwrap();
+        assert!(err.to_string().contains("session token"));
+        assert!(!input_path.exists());
+    }
+}
@@ -0,0 +1,89 @@
+use std::env;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::cargo_input_dir;
+
+/// The Advent of Code site, unless configured otherwise.
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Per-user settings, kept out of the repository.  Every field is optional, and
+/// environment variables take precedence over the file:
+///
+/// ```toml
+/// session = "53616c74..."   # or AOC_SESSION
+/// base_url = "http://localhost:8080"   # or AOC_BASE_URL
+/// input_dir = "/home/me/aoc-inputs"   # or AOC_INPUT_DIR
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub session: Option<String>,
+    pub base_url: Option<String>,
+    pub input_dir: Option<PathBuf>,
+}
+
+/// `AOC_CONFIG` if set, otherwise `aoc/config.toml` in the user's config directory.
+pub fn config_file_path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os("AOC_CONFIG") {
+        return Some(PathBuf::from(path));
+    }
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .or_else(|| env::var_os("APPDATA").map(PathBuf::from))?;
+    Some(config_dir.join("aoc").join("config.toml"))
+}
+
+impl Config {
+    /// Reads the config file, if there is one, then applies any environment overrides.
+    pub fn load() -> Result<Config> {
+        let mut config = match config_file_path() {
+            Some(path) => match fs::read_to_string(&path) {
+                Ok(text) => toml::from_str(&text)
+                    .with_context(|| format!("Failed to parse {}", path.display()))?,
+                Err(err) if err.kind() == ErrorKind::NotFound => Config::default(),
+                Err(err) => {
+                    return Err(err).with_context(|| format!("Failed to read {}", path.display()))
+                }
+            },
+            None => Config::default(),
+        };
+        if let Ok(session) = env::var("AOC_SESSION") {
+            config.session = Some(session);
+        }
+        if let Ok(base_url) = env::var("AOC_BASE_URL") {
+            config.base_url = Some(base_url);
+        }
+        if let Some(input_dir) = env::var_os("AOC_INPUT_DIR") {
+            config.input_dir = Some(PathBuf::from(input_dir));
+        }
+        Ok(config)
+    }
+
+    pub fn base_url(&self) -> &str {
+        self.base_url.as_deref().unwrap_or(DEFAULT_BASE_URL)
+    }
+
+    /// Where inputs are kept, falling back to the crate's inputs directory.
+    pub fn input_dir(&self) -> PathBuf {
+        self.input_dir.clone().unwrap_or_else(cargo_input_dir)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_parse() {
+        let config: Config = toml::from_str("session = \"abc\"\n").unwrap();
+        assert_eq!(config.session.as_deref(), Some("abc"));
+        assert_eq!(config.base_url(), DEFAULT_BASE_URL);
+        assert!(toml::from_str::<Config>("sesion = \"abc\"\n").is_err());
+    }
+}
@@ -0,0 +1,307 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use itertools::Itertools;
+
+use crate::answer::Answer;
+use crate::bench::Stats;
+use crate::input::InputSource;
+use crate::known_answers::{self, KnownAnswer};
+use crate::runner::{self, render_table, PartReport, Status};
+use crate::solution::Registration;
+
+/// One of several inputs for the same puzzle, such as each team member's, with the
+/// answers accepted for it if they are known.  The answers for `NAME` or `NAME.txt`
+/// are in `NAME.answer`, written like a day's table in answers.toml:
+///
+/// ```toml
+/// part1 = 1466
+/// part2 = 1491
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SharedInput {
+    pub name: String,
+    pub input_path: PathBuf,
+    pub answers: Vec<KnownAnswer>,
+}
+
+/// Every input in `dir`, in name order, with its answers for the given day.  Hidden
+/// files are ignored.
+pub fn discover(dir: &Path, year: usize, day: usize) -> Result<Vec<SharedInput>> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))? {
+        let path = entry
+            .with_context(|| format!("Failed to read {}", dir.display()))?
+            .path();
+        let hidden = path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+        let answer = path
+            .extension()
+            .is_some_and(|extension| extension == "answer");
+        if path.is_file() && !hidden && !answer {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|input_path| {
+            let answer_path = input_path.with_extension("answer");
+            let answers = if answer_path.is_file() {
+                fs::read_to_string(&answer_path)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|text| known_answers::parse_day(year, day, &text))
+                    .with_context(|| format!("Failed to read {}", answer_path.display()))?
+            } else {
+                Vec::new()
+            };
+            let name = input_path
+                .file_stem()
+                .map_or_else(String::new, |stem| stem.to_string_lossy().to_string());
+            Ok(SharedInput {
+                name,
+                input_path,
+                answers,
+            })
+        })
+        .collect()
+}
+
+/// How one part went against one input.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CheckResult {
+    /// The answer matched the expected one.
+    Correct,
+    Mismatch,
+    /// The part ran, but there was no expected answer to compare with.
+    Unchecked,
+    /// The part didn't produce an answer to compare.
+    Status(Status),
+}
+
+impl fmt::Display for CheckResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CheckResult::Correct => write!(f, "correct"),
+            CheckResult::Mismatch => write!(f, "MISMATCH"),
+            CheckResult::Unchecked => write!(f, "unchecked"),
+            CheckResult::Status(status) => write!(f, "{}", status),
+        }
+    }
+}
+
+/// One part run against one of the inputs.
+pub struct CrossCheck {
+    pub name: String,
+    pub report: PartReport,
+    pub expected: Option<Answer>,
+}
+
+impl CrossCheck {
+    /// Compares the answer with the expected one if there is one, otherwise gives the
+    /// run's status.
+    pub fn result(&self) -> CheckResult {
+        match (&self.report.status, &self.expected) {
+            (Status::Ok, Some(expected)) if self.report.answer.as_ref() == Some(expected) => {
+                CheckResult::Correct
+            }
+            (Status::Ok, Some(_)) => CheckResult::Mismatch,
+            (Status::Ok, None) => CheckResult::Unchecked,
+            (status, _) => CheckResult::Status(status.clone()),
+        }
+    }
+
+    /// Whether the answer disagreed with the expected one, or the part failed.
+    pub fn is_failure(&self) -> bool {
+        matches!(
+            self.result(),
+            CheckResult::Mismatch | CheckResult::Status(Status::Error(_) | Status::TimedOut)
+        )
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut json = self.report.to_json();
+        json["input"] = self.name.clone().into();
+        json["expected"] = self.expected.as_ref().map(Answer::to_string).into();
+        json["result"] = self.result().to_string().into();
+        json
+    }
+}
+
+/// Runs `puzzles` against each input in turn, so that the timings are comparable.
+pub fn run(
+    registration: &'static Registration,
+    puzzles: &[usize],
+    inputs: &[SharedInput],
+    timeout: Option<Duration>,
+) -> Vec<CrossCheck> {
+    inputs
+        .iter()
+        .flat_map(|input| {
+            let source = InputSource::File(input.input_path.clone());
+            runner::run_day(registration, puzzles, &source, timeout)
+                .into_iter()
+                .map(|report| CrossCheck {
+                    name: input.name.clone(),
+                    expected: input
+                        .answers
+                        .iter()
+                        .find(|k| k.part == report.puzzle)
+                        .map(|k| k.answer.clone()),
+                    report,
+                })
+                .collect_vec()
+        })
+        .collect()
+}
+
+/// Renders the checks as a plain-text table, one row per input and part.
+pub fn format_results(checks: &[CrossCheck]) -> String {
+    let header = [
+        "Input",
+        "Part",
+        "Answer",
+        "Expected",
+        "Parse µs",
+        "Solve µs",
+        "Result",
+    ];
+    let mut last_name = None;
+    let rows = checks
+        .iter()
+        .map(|c| {
+            let timed = matches!(c.report.status, Status::Ok | Status::Unsolved);
+            // Both parts share a parse, so it's only shown against the first.
+            let first = last_name.replace(&c.name) != Some(&c.name);
+            vec![
+                c.name.clone(),
+                c.report.puzzle.to_string(),
+                c.report
+                    .answer
+                    .as_ref()
+                    .map_or(String::new(), Answer::single_line),
+                c.expected
+                    .as_ref()
+                    .map_or(String::new(), Answer::single_line),
+                if timed && first {
+                    c.report.parse_duration.as_micros().to_string()
+                } else {
+                    String::new()
+                },
+                if timed {
+                    c.report.solve_duration.as_micros().to_string()
+                } else {
+                    String::new()
+                },
+                c.result().to_string(),
+            ]
+        })
+        .collect_vec();
+    render_table(&header, &rows)
+}
+
+/// Summarises how the parse and each part's timings vary between inputs.
+pub fn format_spread(checks: &[CrossCheck]) -> String {
+    let timed = checks
+        .iter()
+        .filter(|c| matches!(c.report.status, Status::Ok | Status::Unsolved))
+        .collect_vec();
+    let parse = timed
+        .iter()
+        .unique_by(|c| &c.name)
+        .map(|c| c.report.parse_duration)
+        .collect_vec();
+    let parts = timed
+        .iter()
+        .map(|c| c.report.puzzle)
+        .unique()
+        .sorted()
+        .map(|puzzle| {
+            let samples = timed
+                .iter()
+                .filter(|c| c.report.puzzle == puzzle)
+                .map(|c| c.report.solve_duration)
+                .collect_vec();
+            (format!("Puzzle {}", puzzle), samples)
+        });
+
+    std::iter::once(("Parse".to_string(), parse))
+        .chain(parts)
+        .filter(|(_, samples)| !samples.is_empty())
+        .map(|(label, samples)| {
+            let stats = Stats::from_samples(&samples);
+            let max = samples.iter().max().unwrap();
+            format!(
+                "{} over {} inputs: min {} µs, median {} µs, max {} µs, stddev {} µs",
+                label,
+                samples.len(),
+                stats.min.as_micros(),
+                stats.median.as_micros(),
+                max.as_micros(),
+                stats.stddev.as_micros()
+            )
+        })
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::find_solution;
+    use crate::test_util::{TempDir, DEPTHS};
+
+    #[test]
+    fn check_cross_check() {
+        let dir = TempDir::new("cross-check");
+        fs::write(dir.join("alice.txt"), DEPTHS).unwrap();
+        fs::write(dir.join("alice.answer"), "part1 = 7\npart2 = 5\n").unwrap();
+        fs::write(dir.join("bob.txt"), "1\n2\n").unwrap();
+        fs::write(dir.join("bob.answer"), "part1 = 2\n").unwrap();
+        fs::write(dir.join("carol"), "3\n2\n1\n").unwrap();
+        fs::write(dir.join(".hidden"), "x\n").unwrap();
+
+        let inputs = discover(dir.path(), 2021, 1).unwrap();
+        assert_eq!(
+            inputs.iter().map(|i| i.name.as_str()).collect_vec(),
+            vec!["alice", "bob", "carol"]
+        );
+        assert_eq!(inputs[0].answers.len(), 2);
+
+        let checks = run(find_solution(2021, 1).unwrap(), &[1, 2], &inputs, None);
+        assert_eq!(
+            checks
+                .iter()
+                .map(|c| (c.name.as_str(), c.report.puzzle, c.result()))
+                .collect_vec(),
+            vec![
+                ("alice", 1, CheckResult::Correct),
+                ("alice", 2, CheckResult::Correct),
+                ("bob", 1, CheckResult::Mismatch),
+                ("bob", 2, CheckResult::Unchecked),
+                ("carol", 1, CheckResult::Unchecked),
+                ("carol", 2, CheckResult::Unchecked),
+            ]
+        );
+        assert_eq!(checks.iter().filter(|c| c.is_failure()).count(), 1);
+        assert_eq!(checks[2].to_json()["expected"], "2");
+        assert_eq!(checks[2].to_json()["result"], "MISMATCH");
+
+        let spread = format_spread(&checks);
+        assert_eq!(spread.lines().count(), 3);
+        assert!(spread.starts_with("Parse over 3 inputs: min "));
+    }
+
+    #[test]
+    fn check_bad_answer_file() {
+        let dir = TempDir::new("cross-check-bad");
+        fs::write(dir.join("alice.txt"), "1\n").unwrap();
+        fs::write(dir.join("alice.answer"), "part3 = 1\n").unwrap();
+        let err = discover(dir.path(), 2021, 1).unwrap_err();
+        assert!(format!("{:#}", err).contains("alice.answer"));
+    }
+}
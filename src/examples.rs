@@ -0,0 +1,145 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::answer::Answer;
+use crate::input::Input;
+use crate::{describe_missing_solution, find_solution, registry};
+
+/// An example from a puzzle description, stored as `YYYY-DD-P.txt` for part `P`, with
+/// the expected answer in `YYYY-DD-P.answer` alongside.  Further examples for the same
+/// part add a name: `YYYY-DD-P-NAME.txt`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Example {
+    pub year: usize,
+    pub day: usize,
+    pub part: usize,
+    pub name: Option<String>,
+    pub input_path: PathBuf,
+}
+
+/// The examples directory checked in under the crate's inputs.
+pub fn examples_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("inputs")
+        .join("examples")
+}
+
+/// Every example in `dir`, in year, day, part and name order.  Files other than `.txt`
+/// are ignored, but a `.txt` file with a malformed name is an error, so that a typo
+/// doesn't silently drop an example.
+pub fn discover(dir: &Path) -> Result<Vec<Example>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err).with_context(|| format!("Failed to read {}", dir.display())),
+    };
+    let mut examples = Vec::new();
+    for entry in entries {
+        let path = entry
+            .with_context(|| format!("Failed to read {}", dir.display()))?
+            .path();
+        if path.extension().is_some_and(|extension| extension == "txt") {
+            examples.push(Example::from_path(&path)?);
+        }
+    }
+    examples
+        .sort_by(|a, b| (a.year, a.day, a.part, &a.name).cmp(&(b.year, b.day, b.part, &b.name)));
+    Ok(examples)
+}
+
+impl Example {
+    fn from_path(path: &Path) -> Result<Example> {
+        let invalid = || {
+            anyhow!(
+                "Example {} is not named YYYY-DD-P.txt or YYYY-DD-P-NAME.txt",
+                path.display()
+            )
+        };
+        let stem = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or_else(invalid)?;
+        let mut fields = stem.splitn(4, '-');
+        let mut number = || -> Result<usize> {
+            fields
+                .next()
+                .and_then(|field| field.parse().ok())
+                .ok_or_else(invalid)
+        };
+        let (year, day, part) = (number()?, number()?, number()?);
+        if !(1..=2).contains(&part) {
+            return Err(invalid());
+        }
+        Ok(Example {
+            year,
+            day,
+            part,
+            name: fields.next().map(|name| name.to_string()),
+            input_path: path.to_path_buf(),
+        })
+    }
+
+    pub fn answer_path(&self) -> PathBuf {
+        self.input_path.with_extension("answer")
+    }
+
+    /// A name such as `2022-01-2` or `2022-01-2-uneven`.
+    pub fn label(&self) -> String {
+        let label = format!("{}-{:02}-{}", self.year, self.day, self.part);
+        match &self.name {
+            Some(name) => format!("{}-{}", label, name),
+            None => label,
+        }
+    }
+
+    /// The expected answer, or `None` if the answer file is missing or empty (for
+    /// example, a part that hasn't been unlocked yet).
+    pub fn expected(&self) -> Result<Option<Answer>> {
+        let path = self.answer_path();
+        match fs::read_to_string(&path) {
+            Ok(text) => {
+                let text = text.replace("\r\n", "\n");
+                let text = text.trim_end_matches('\n');
+                Ok((!text.is_empty()).then(|| Answer::from(text)))
+            }
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    /// Runs the example's part through the registered solution.
+    pub fn run(&self) -> Result<Option<Answer>> {
+        let registration = find_solution(self.year, self.day)
+            .ok_or_else(|| anyhow!(describe_missing_solution(registry(), self.year, self.day)))?;
+        let input = Input::from_file(&self.input_path)?;
+        registration.run(self.part, &input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_from_path() {
+        let example = Example::from_path(Path::new("examples/2022-01-2-uneven.txt")).unwrap();
+        assert_eq!((example.year, example.day, example.part), (2022, 1, 2));
+        assert_eq!(example.name.as_deref(), Some("uneven"));
+        assert_eq!(example.label(), "2022-01-2-uneven");
+        assert_eq!(
+            example.answer_path(),
+            Path::new("examples/2022-01-2-uneven.answer")
+        );
+
+        assert!(Example::from_path(Path::new("examples/2022-01-3.txt")).is_err());
+        assert!(Example::from_path(Path::new("examples/2022-01.txt")).is_err());
+    }
+
+    #[test]
+    fn check_checked_in_examples_are_well_formed() {
+        assert!(!discover(&examples_dir()).unwrap().is_empty());
+    }
+}
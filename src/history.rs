@@ -0,0 +1,316 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::bench::BenchReport;
+
+/// Differences smaller than this are treated as noise, however large they are
+/// relative to the baseline.  Sub-microsecond parts otherwise flap between runs.
+const NOISE_FLOOR_US: f64 = 1.0;
+
+/// One benchmarked part, as recorded in the history file.  The history file holds one
+/// entry per line, in the order they were recorded.  Only `aoc bench` records entries:
+/// the timings `aoc run` prints come from a single run, too noisy to compare.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub year: usize,
+    pub day: usize,
+    pub part: usize,
+    pub commit: String,
+    /// Seconds since the Unix epoch.
+    pub recorded_at: u64,
+    /// The parse is shared by every part benchmarked in the same run, so each of their
+    /// entries repeats it.
+    pub parse_median_us: f64,
+    pub solve_median_us: f64,
+}
+
+/// What a comparison times: the parse shared by a day's parts, or one part's solve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Stage {
+    Parse,
+    Part(usize),
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Part(part) => write!(f, "part {}", part),
+        }
+    }
+}
+
+/// One recorded median time for a stage.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sample {
+    pub micros: f64,
+    pub commit: String,
+}
+
+pub fn entries_from_report(
+    report: &BenchReport,
+    commit: &str,
+    recorded_at: u64,
+) -> Vec<HistoryEntry> {
+    report
+        .parts
+        .iter()
+        .map(|part| HistoryEntry {
+            year: report.year,
+            day: report.day,
+            part: part.puzzle,
+            commit: commit.to_string(),
+            recorded_at,
+            parse_median_us: report.parse.median.as_secs_f64() * 1e6,
+            solve_median_us: part.solve.median.as_secs_f64() * 1e6,
+        })
+        .collect()
+}
+
+/// The short hash of the checked-out commit, with `-dirty` appended if there are
+/// uncommitted changes, or `unknown` outside a git checkout.
+pub fn current_commit() -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(hash) => match git(&["status", "--porcelain", "--untracked-files=no"]) {
+            Some(changes) if !changes.is_empty() => format!("{}-dirty", hash),
+            _ => hash,
+        },
+        None => "unknown".to_string(),
+    }
+}
+
+/// Reads every entry from the history file.  A missing file is an empty history.
+pub fn load(path: &Path) -> Result<Vec<HistoryEntry>> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err).with_context(|| format!("Failed to read {}", path.display())),
+    };
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line).with_context(|| {
+                format!("Failed to parse line {} of {}", index + 1, path.display())
+            })
+        })
+        .collect()
+}
+
+pub fn append(path: &Path, entries: &[HistoryEntry]) -> Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    for entry in entries {
+        let line = serde_json::to_string(entry).expect("History entries always serialize");
+        writeln!(file, "{}", line)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// The most recent run of a stage, against the run before it and the fastest run
+/// before it.
+pub struct Comparison {
+    pub year: usize,
+    pub day: usize,
+    pub stage: Stage,
+    pub latest: Sample,
+    pub previous: Option<Sample>,
+    pub best: Option<Sample>,
+    pub slower_than_previous: bool,
+    pub slower_than_best: bool,
+}
+
+impl Comparison {
+    pub fn is_regression(&self) -> bool {
+        self.slower_than_previous || self.slower_than_best
+    }
+}
+
+/// Compares the latest run of every day's parse and every part's solve with their
+/// earlier runs.  A run counts as slower if it takes more than `threshold` (e.g. 0.1
+/// for 10%) longer than the baseline, and by more than the noise floor.
+pub fn compare(entries: &[HistoryEntry], threshold: f64) -> Vec<Comparison> {
+    let mut by_stage: BTreeMap<(usize, usize, Stage), Vec<Sample>> = BTreeMap::new();
+    // Entries written by the same `aoc bench` run share its parse.
+    let same_run = |a: &HistoryEntry, b: &HistoryEntry| {
+        (a.year, a.day, &a.commit, a.recorded_at) == (b.year, b.day, &b.commit, b.recorded_at)
+    };
+    for run in entries.chunk_by(same_run) {
+        let (year, day) = (run[0].year, run[0].day);
+        let sample = |micros, commit: &str| Sample {
+            micros,
+            commit: commit.to_string(),
+        };
+        by_stage
+            .entry((year, day, Stage::Parse))
+            .or_default()
+            .push(sample(run[0].parse_median_us, &run[0].commit));
+        for entry in run {
+            by_stage
+                .entry((year, day, Stage::Part(entry.part)))
+                .or_default()
+                .push(sample(entry.solve_median_us, &entry.commit));
+        }
+    }
+
+    let slower = |latest: &Sample, baseline: Option<&Sample>| {
+        baseline.is_some_and(|baseline| {
+            let delta = latest.micros - baseline.micros;
+            delta > NOISE_FLOOR_US && delta > baseline.micros * threshold
+        })
+    };
+
+    by_stage
+        .into_iter()
+        .map(|((year, day, stage), runs)| {
+            let (latest, earlier) = runs.split_last().expect("Every key has a run");
+            let previous = earlier.last();
+            let best = earlier.iter().min_by(|a, b| a.micros.total_cmp(&b.micros));
+            Comparison {
+                year,
+                day,
+                stage,
+                slower_than_previous: slower(latest, previous),
+                slower_than_best: slower(latest, best),
+                latest: latest.clone(),
+                previous: previous.cloned(),
+                best: best.cloned(),
+            }
+        })
+        .collect()
+}
+
+/// Renders the comparisons as a plain-text table, one row per stage.
+pub fn format_comparisons(comparisons: &[Comparison]) -> String {
+    let baseline = |sample: &Option<Sample>| match sample {
+        Some(sample) => format!("{:.1} ({})", sample.micros, sample.commit),
+        None => "-".to_string(),
+    };
+    let lines = comparisons
+        .iter()
+        .map(|c| {
+            let verdict = match (c.slower_than_previous, c.slower_than_best) {
+                (true, true) => "REGRESSION vs previous and best",
+                (true, false) => "REGRESSION vs previous",
+                (false, true) => "REGRESSION vs best",
+                (false, false) => "ok",
+            };
+            format!(
+                "{} day {:>2} {}: {:.1} µs ({}); previous {}; best {}; {}",
+                c.year,
+                c.day,
+                c.stage,
+                c.latest.micros,
+                c.latest.commit,
+                baseline(&c.previous),
+                baseline(&c.best),
+                verdict
+            )
+        })
+        .collect_vec();
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn entry(commit: &str, solve_median_us: f64) -> HistoryEntry {
+        HistoryEntry {
+            year: 2021,
+            day: 8,
+            part: 1,
+            commit: commit.to_string(),
+            recorded_at: 0,
+            parse_median_us: 100.0,
+            solve_median_us,
+        }
+    }
+
+    #[test]
+    fn check_compare() {
+        let entries = vec![entry("a", 100.0), entry("b", 150.0), entry("c", 160.0)];
+        let comparisons = compare(&entries, 0.1);
+        assert_eq!(comparisons.len(), 2);
+        assert_eq!(comparisons[0].stage, Stage::Parse);
+        assert!(!comparisons[0].is_regression());
+        let c = &comparisons[1];
+        assert_eq!(c.stage, Stage::Part(1));
+        assert_eq!(c.latest.commit, "c");
+        assert_eq!(c.previous.as_ref().unwrap().commit, "b");
+        assert_eq!(c.best.as_ref().unwrap().commit, "a");
+        // 160 is within 10% of 150, but not of 100.
+        assert!(!c.slower_than_previous);
+        assert!(c.slower_than_best);
+    }
+
+    #[test]
+    fn check_shared_parse_counts_once() {
+        let run = |commit: &str, recorded_at, parse_median_us| {
+            (1..=2)
+                .map(|part| HistoryEntry {
+                    part,
+                    recorded_at,
+                    parse_median_us,
+                    ..entry(commit, 10.0)
+                })
+                .collect_vec()
+        };
+        let entries = [run("a", 1, 100.0), run("b", 2, 200.0)].concat();
+        let comparisons = compare(&entries, 0.1);
+        assert_eq!(
+            comparisons.iter().map(|c| c.stage).collect_vec(),
+            vec![Stage::Parse, Stage::Part(1), Stage::Part(2)]
+        );
+        let parse = &comparisons[0];
+        assert_eq!(parse.latest.micros, 200.0);
+        assert_eq!(parse.previous.as_ref().unwrap().micros, 100.0);
+        assert!(parse.slower_than_previous);
+        assert!(!comparisons[1].is_regression());
+        assert!(format_comparisons(&comparisons)
+            .starts_with("2021 day  8 parse: 200.0 µs (b); previous 100.0 (a); best 100.0 (a);"));
+    }
+
+    #[test]
+    fn check_noise_floor() {
+        let mut fast = entry("a", 0.1);
+        fast.parse_median_us = 0.1;
+        let mut slow = entry("b", 0.5);
+        slow.parse_median_us = 0.1;
+        assert!(compare(&[fast, slow], 0.1)
+            .iter()
+            .all(|c| !c.is_regression()));
+    }
+
+    #[test]
+    fn check_load_and_append() {
+        let dir = TempDir::new("history");
+        let path = dir.join("history.jsonl");
+        assert!(load(&path).unwrap().is_empty());
+        append(&path, &[entry("a", 1.0)]).unwrap();
+        append(&path, &[entry("b", 2.0)]).unwrap();
+        assert_eq!(load(&path).unwrap(), vec![entry("a", 1.0), entry("b", 2.0)]);
+    }
+}
@@ -0,0 +1,141 @@
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+/// A puzzle input, loaded once.  Each solution chooses how to view it: as the whole
+/// text, as blank-line-separated sections, or as lines.
+#[derive(Debug, Clone)]
+pub struct Input {
+    text: String,
+}
+
+impl Input {
+    pub fn new(text: &str) -> Input {
+        Input {
+            text: text.replace("\r\n", "\n"),
+        }
+    }
+
+    pub fn from_file(input_path: &Path) -> Result<Input> {
+        let text = fs::read_to_string(input_path)
+            .with_context(|| format!("Failed to read input file {}", input_path.display()))?;
+        Ok(Input::new(&text))
+    }
+
+    /// The whole input, exactly as loaded (apart from normalised line endings).
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.text.lines()
+    }
+
+    /// Groups of lines separated by one or more blank lines.  Each section has no
+    /// leading or trailing newline.
+    pub fn sections(&self) -> impl Iterator<Item = &str> {
+        self.text
+            .split("\n\n")
+            .map(|section| section.trim_matches('\n'))
+            .filter(|section| !section.is_empty())
+    }
+}
+
+/// Where to read a day's input from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    /// Resolves the `--input` argument for the day.  `-` means standard input, and a
+    /// directory means the day's file inside it.  Without an argument, the day's file in
+    /// `input_dir` is used.
+    pub fn resolve(input: Option<&str>, input_dir: &Path, year: usize, day: usize) -> InputSource {
+        match input {
+            Some("-") => InputSource::Stdin,
+            Some(input) if Path::new(input).is_dir() => {
+                InputSource::File(crate::input_file_path(Path::new(input), year, day))
+            }
+            Some(input) => InputSource::File(PathBuf::from(input)),
+            None => InputSource::File(crate::input_file_path(input_dir, year, day)),
+        }
+    }
+
+    pub fn read(&self) -> Result<Input> {
+        match self {
+            InputSource::Stdin => {
+                let mut text = String::new();
+                io::stdin()
+                    .read_to_string(&mut text)
+                    .context("Failed to read input from standard input")?;
+                Ok(Input::new(&text))
+            }
+            InputSource::File(path) => Input::from_file(path),
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputSource::Stdin => write!(f, "standard input"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+impl From<&str> for Input {
+    fn from(text: &str) -> Input {
+        Input::new(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+
+    #[test]
+    fn check_sections() {
+        let input = Input::from("1\n2\n\n3\n\n\n4\n5\n");
+        assert_eq!(input.sections().collect_vec(), vec!["1\n2", "3", "4\n5"]);
+        assert_eq!(
+            input.lines().collect_vec(),
+            vec!["1", "2", "", "3", "", "", "4", "5"]
+        );
+    }
+
+    #[test]
+    fn check_resolve_input_source() {
+        let dir = std::env::temp_dir();
+        let in_dir = dir.join("input-2021-day01");
+        assert_eq!(
+            InputSource::resolve(None, Path::new("inputs"), 2021, 1),
+            InputSource::File(PathBuf::from("inputs/input-2021-day01"))
+        );
+        assert_eq!(
+            InputSource::resolve(dir.to_str(), Path::new("inputs"), 2021, 1),
+            InputSource::File(in_dir)
+        );
+        assert_eq!(
+            InputSource::resolve(Some("my-input.txt"), Path::new("inputs"), 2021, 1),
+            InputSource::File(PathBuf::from("my-input.txt"))
+        );
+        assert_eq!(
+            InputSource::resolve(Some("-"), Path::new("inputs"), 2021, 1),
+            InputSource::Stdin
+        );
+    }
+
+    #[test]
+    fn check_windows_line_endings() {
+        let input = Input::from("1\r\n2\r\n\r\n3\r\n");
+        assert_eq!(input.text(), "1\n2\n\n3\n");
+        assert_eq!(input.sections().collect_vec(), vec!["1\n2", "3"]);
+    }
+}
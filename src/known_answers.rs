@@ -0,0 +1,235 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use itertools::Itertools;
+
+use crate::answer::Answer;
+use crate::input::InputSource;
+use crate::runner::{run_day, Status};
+use crate::{describe_missing_solution, find_solution, registry};
+
+/// An answer accepted by the Advent of Code website.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KnownAnswer {
+    pub year: usize,
+    pub day: usize,
+    pub part: usize,
+    pub answer: Answer,
+}
+
+/// The known-answers file checked in at the root of the crate.
+pub fn answers_file_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("answers.toml")
+}
+
+pub fn load(path: &Path) -> Result<Vec<KnownAnswer>> {
+    let text =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    parse(&text).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// Parses answers grouped by year and day, in year and day order:
+///
+/// ```toml
+/// [2021.1]
+/// part1 = 1466
+/// part2 = 1491
+/// ```
+///
+/// Answers may be integers or strings.
+pub fn parse(text: &str) -> Result<Vec<KnownAnswer>> {
+    let years: BTreeMap<String, BTreeMap<String, BTreeMap<String, toml::Value>>> =
+        toml::from_str(text)?;
+    let mut known = Vec::new();
+    for (year, days) in years {
+        let year = year
+            .parse()
+            .with_context(|| format!("Invalid year {:?}", year))?;
+        for (day, parts) in days {
+            let day = day
+                .parse()
+                .with_context(|| format!("Invalid day {:?} of {}", day, year))?;
+            known.extend(parse_parts(year, day, parts)?);
+        }
+    }
+    known.sort_by_key(|k| (k.year, k.day, k.part));
+    Ok(known)
+}
+
+/// Parses one day's answers, given as `part1` and `part2` keys.
+fn parse_parts(
+    year: usize,
+    day: usize,
+    parts: BTreeMap<String, toml::Value>,
+) -> Result<Vec<KnownAnswer>> {
+    let mut known = Vec::new();
+    for (part, value) in parts {
+        let part = match part.as_str() {
+            "part1" => 1,
+            "part2" => 2,
+            _ => {
+                return Err(anyhow!(
+                    "Invalid part {:?} for day {} of {}",
+                    part,
+                    day,
+                    year
+                ))
+            }
+        };
+        let answer = match value {
+            toml::Value::Integer(value) if value < 0 => Answer::from(value as isize),
+            toml::Value::Integer(value) => Answer::from(value as usize),
+            toml::Value::String(value) => Answer::from(value),
+            _ => {
+                return Err(anyhow!(
+                    "Answer for day {}, part {} of {} must be an integer or string",
+                    day,
+                    part,
+                    year
+                ))
+            }
+        };
+        known.push(KnownAnswer {
+            year,
+            day,
+            part,
+            answer,
+        });
+    }
+    Ok(known)
+}
+
+/// Parses a file holding the answers for a single day, in the same form as a day's
+/// table in the known-answers file.
+pub fn parse_day(year: usize, day: usize, text: &str) -> Result<Vec<KnownAnswer>> {
+    let parts: BTreeMap<String, toml::Value> = toml::from_str(text)?;
+    parse_parts(year, day, parts)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    Correct,
+    Wrong(Answer),
+    Unsolved,
+    MissingInput,
+    Error(String),
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::Wrong(answer) => write!(f, "WRONG: got {}", answer.single_line()),
+            Verdict::Unsolved => write!(f, "unsolved"),
+            Verdict::MissingInput => write!(f, "missing input"),
+            Verdict::Error(err) => write!(f, "error: {}", err),
+        }
+    }
+}
+
+pub struct Verification {
+    pub known: KnownAnswer,
+    pub verdict: Verdict,
+}
+
+/// Runs the solution for each known answer against the input given by `input_path`,
+/// parsing each day's input only once.
+pub fn verify(
+    known: &[KnownAnswer],
+    input_path: impl Fn(usize, usize) -> PathBuf,
+) -> Vec<Verification> {
+    known
+        .iter()
+        .group_by(|k| (k.year, k.day))
+        .into_iter()
+        .flat_map(|((year, day), group)| {
+            let group = group.collect_vec();
+            let verdicts = match find_solution(year, day) {
+                Some(registration) => {
+                    let puzzles = group.iter().map(|k| k.part).collect_vec();
+                    let source = InputSource::File(input_path(year, day));
+                    run_day(registration, &puzzles, &source, None)
+                        .into_iter()
+                        .zip(&group)
+                        .map(|(report, known)| match report.status {
+                            Status::Ok if report.answer.as_ref() == Some(&known.answer) => {
+                                Verdict::Correct
+                            }
+                            Status::Ok => Verdict::Wrong(report.answer.unwrap()),
+                            Status::Unsolved => Verdict::Unsolved,
+                            Status::MissingInput => Verdict::MissingInput,
+                            Status::TimedOut => Verdict::Error(Status::TimedOut.to_string()),
+                            Status::Error(err) => Verdict::Error(err),
+                        })
+                        .collect_vec()
+                }
+                None => group
+                    .iter()
+                    .map(|_| Verdict::Error(describe_missing_solution(registry(), year, day)))
+                    .collect_vec(),
+            };
+            group
+                .into_iter()
+                .zip(verdicts)
+                .map(|(known, verdict)| Verification {
+                    known: known.clone(),
+                    verdict,
+                })
+                .collect_vec()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn check_parse() {
+        let known =
+            parse("[2023.1]\npart2 = 281\npart1 = 142\n\n[2022.10]\npart2 = \"EHZFZHCZ\"\n")
+                .unwrap();
+        assert_eq!(
+            known
+                .iter()
+                .map(|k| (k.year, k.day, k.part, k.answer.to_string()))
+                .collect_vec(),
+            vec![
+                (2022, 10, 2, "EHZFZHCZ".to_string()),
+                (2023, 1, 1, "142".to_string()),
+                (2023, 1, 2, "281".to_string()),
+            ]
+        );
+        assert!(parse("[2023.1]\npart3 = 1\n").is_err());
+    }
+
+    #[test]
+    fn check_verify() {
+        let dir = TempDir::new("verify");
+        let path = dir.join("input-2021-day01");
+        fs::write(&path, "1\n3\n2\n4\n5\n").unwrap();
+        let known = parse("[2021.1]\npart1 = 3\npart2 = 4\n[2021.2]\npart2 = 900\n").unwrap();
+        let verdicts = verify(&known, |_, day| {
+            if day == 1 {
+                path.clone()
+            } else {
+                PathBuf::from("/no/such/input")
+            }
+        })
+        .into_iter()
+        .map(|v| v.verdict)
+        .collect_vec();
+        assert_eq!(
+            verdicts,
+            vec![
+                Verdict::Correct,
+                Verdict::Wrong(Answer::from(2usize)),
+                Verdict::MissingInput
+            ]
+        );
+    }
+}
@@ -1,7 +1,19 @@
+// The helpers written before the registry are kept as they were, rather than rewritten
+// to satisfy lints that arrived later.
+#[allow(
+    clippy::bool_assert_comparison,
+    clippy::clone_on_copy,
+    clippy::extra_unused_lifetimes,
+    clippy::manual_abs_diff,
+    clippy::map_flatten,
+    clippy::needless_borrow,
+    clippy::unnecessary_sort_by,
+    clippy::unnecessary_to_owned,
+    clippy::useless_vec
+)]
 mod old {
     pub mod bingo;
     pub mod bitaccumulator;
-    pub mod calories;
     pub mod command;
     pub mod coordinates;
     pub mod crabs;
@@ -12,379 +24,99 @@ mod old {
     pub mod valuemap;
 }
 
-use std::convert::TryInto;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+pub mod alloc_stats;
+mod answer;
+pub mod bench;
+pub mod calendar;
+pub mod client;
+pub mod config;
+pub mod cross_check;
+pub mod examples;
+pub mod history;
+mod input;
+pub mod known_answers;
+pub mod logging;
+pub mod progress;
+pub mod puzzle_page;
+pub mod runner;
+pub mod scaffold;
+mod solution;
+pub mod submit;
+#[cfg(test)]
+mod test_util;
+pub mod watch;
+mod y2021;
+mod y2022;
+mod y2023;
+
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
-use anyhow::{anyhow, Result};
-use itertools::Itertools;
-use regex::Regex;
+use anyhow::{anyhow, Context, Result};
 
-use crate::old::bingo::BingoState;
-use crate::old::bitaccumulator::DiagsReport;
-use crate::old::calories::CalorieCounter;
-use crate::old::command::Command;
-use crate::old::coordinates::{GridCounter, LineSegment};
-use crate::old::lanternfish::LanternShoal;
-use crate::old::position::Position;
-use crate::old::rockpaperscissors::score_guide_round;
-use crate::old::segment_display::{SegmentDisplay, SegmentMapping};
-use crate::old::crabs;
+pub use crate::answer::Answer;
+pub use crate::input::{Input, InputSource};
+pub use crate::solution::{
+    describe_missing_solution, find_solution, registry, BadInput, DayRun, PartRun, Progress,
+    Registration, RunEvent, Solution,
+};
 
+/// Runs one puzzle part against the given input.
+///
+/// Returns `Ok(None)` when there is no solution for the part yet.  Errors caused by the
+/// input rather than the solution carry a `BadInput` context.
 pub fn run_solution(
     year: usize,
     day: usize,
     puzzle: usize,
-    input_strings: impl Iterator<Item = String>,
-) -> Option<usize> {
-    match (year, day, puzzle) {
-        (2021, 1, 1) => Some(
-            parse_input(input_strings)
-                .tuple_windows()
-                .filter(|tuple: &(usize, usize)| tuple.1 > tuple.0)
-                .count(),
-        ),
-        (2021, 1, 2) => Some(
-            parse_input(input_strings)
-                .tuple_windows()
-                .map(|tuple: (usize, usize, usize)| tuple.0 + tuple.1 + tuple.2)
-                .tuple_windows()
-                .filter(|tuple: &(usize, usize)| tuple.1 > tuple.0)
-                .count(),
-        ),
-        (2021, 2, 1) => {
-            println!("Solution no longer available");
-            None
-        }
-        (2021, 2, 2) => Some(
-            nonempty_input_lines(input_strings)
-                .map(|command_str| Command::from_str(&command_str))
-                .fold(Position::at_zero(), |pos, com| pos.exec_command(com))
-                .multiply_x_by_depth(),
-        ),
-        (2021, 3, 1) => {
-            let dr = DiagsReport::from_iter(12, binary_from_input(input_strings));
-            Some(dr.gamma_rate() * dr.epsilon_rate())
-        }
-        (2021, 3, 2) => {
-            let dr = DiagsReport::from_iter(12, binary_from_input(input_strings));
-            Some(dr.oxygen_rate() * dr.co2_scrub_rate())
-        }
-        (2021, 4, 1) => Some(
-            BingoState::from_strs(5, nonempty_input_lines(input_strings))
-                .run_until(|b| b.any_complete())
-                .multiply_complete_sum_unmarked_by_last_number()
-                .unwrap(),
-        ),
-        (2021, 4, 2) => Some(
-            BingoState::from_strs(5, nonempty_input_lines(input_strings))
-                .run_until(|b| b.all_complete())
-                .multiply_complete_sum_unmarked_by_last_number()
-                .unwrap(),
-        ),
-        (2021, 5, 1) => Some(
-            nonempty_input_lines(input_strings)
-                .filter_map(|seg_str| LineSegment::from_str(&seg_str).ok())
-                .filter(|ls| ls.is_horiz() || ls.is_vert())
-                .flat_map(|ls| ls.coords())
-                .fold(GridCounter::new(), |gc, coords| gc.add_coords(&coords))
-                .into_values()
-                .filter(|&v| v > 1)
-                .count(),
-        ),
-        (2021, 5, 2) => Some(
-            nonempty_input_lines(input_strings)
-                .filter_map(|seg_str| LineSegment::from_str(&seg_str).ok())
-                .flat_map(|ls| ls.coords())
-                .fold(GridCounter::new(), |gc, coords| gc.add_coords(&coords))
-                .into_values()
-                .filter(|&v| v > 1)
-                .count(),
-        ),
-        (2021, 6, 1) => Some(
-            LanternShoal::from_str(&single_line_from_input(input_strings))
-                .proceed_n_days(80)
-                .count(),
-        ),
-        (2021, 6, 2) => Some(
-            LanternShoal::from_str(&single_line_from_input(input_strings))
-                .proceed_n_days(256)
-                .count(),
-        ),
-        (2021, 7, 1) => {
-            println!("Solution no longer available");
-            None
-        }
-        (2021, 7, 2) => {
-            let inputs: Vec<usize> = single_line_from_input(input_strings)
-                .split(',')
-                .filter_map(|s| s.parse::<usize>().ok())
-                .collect();
-            let min_cost = (0..).fold_while(usize::MAX, |old_cost, new_position| {
-                crabs::fold_step(&inputs, old_cost, new_position)
-            });
-            Some(min_cost.into_inner())
-        }
-        (2021, 8, 1) => Some(
-            nonempty_input_lines(input_strings)
-                .filter_map(|display| {
-                    display
-                        .split(" | ")
-                        .map(|segments| segments.split(' ').map(|s| s.to_string()).collect_vec())
-                        .collect_tuple::<(_, _)>()
-                })
-                .fold(0, |outer_count, (input_segs, output_segs)| {
-                    let mapping =
-                        SegmentMapping::find_valid(&input_segs).expect("No valid mapping");
-                    outer_count
-                        + output_segs.iter().fold(0, |count, seg| {
-                            match SegmentDisplay::from_str_with_mapping(seg, &mapping).to_int() {
-                                Some(1) | Some(4) | Some(7) | Some(8) => count + 1,
-                                _ => count,
-                            }
-                        })
-                }),
-        ),
-        (2021, 8, 2) => Some(
-            nonempty_input_lines(input_strings)
-                .filter_map(|display| {
-                    display
-                        .split(" | ")
-                        .map(|segments| segments.split(' ').map(|s| s.to_string()).collect_vec())
-                        .collect_tuple::<(_, _)>()
-                })
-                .fold(0, |outer_sum, (input_segs, output_segs)| {
-                    let mapping =
-                        SegmentMapping::find_valid(&input_segs).expect("No valid mapping");
-                    outer_sum
-                        + output_segs
-                            .iter()
-                            .fold((0, 1000), |(sum, pos), seg| {
-                                (
-                                    sum + SegmentDisplay::from_str_with_mapping(seg, &mapping)
-                                        .to_int()
-                                        .unwrap()
-                                        * pos,
-                                    pos / 10,
-                                )
-                            })
-                            .0
-                }),
-        ),
-        (2022, 1, 1) => Some(
-            input_strings
-                .fold(CalorieCounter::reset(), CalorieCounter::fold_step)
-                .max,
-        ),
-        (2022, 1, 2) => Some(
-            input_strings
-                .fold(CalorieCounter::reset(), CalorieCounter::fold_step)
-                .top_three_sum(),
-        ),
-        (2022, 2, 1) => {
-            println!("Solution no longer available");
-            None
-        }
-        (2022, 2, 2) => Some(input_strings.map(|s| score_guide_round(&s)).sum()),
-        (2022, 3, 1) => None,
-        (2022, 3, 2) => None,
-        (2022, 4, 1) => None,
-        (2022, 4, 2) => None,
-        (2023, 1, 1) => Some(
-            input_strings
-                .map(|s| fix_calibration_line(&s).expect(&s))
-                .sum(),
-        ),
-        (2023, 1, 2) => Some(
-            input_strings
-                .map(|s| fix_calibration_line_with_string_digits(&s).expect(&s))
-                .sum(),
-        ),
-        _ => {
-            println!("Puzzle solution not yet available");
-            None
+    input: &Input,
+) -> Result<Option<Answer>> {
+    match find_solution(year, day) {
+        Some(registration) => registration.run(puzzle, input),
+        None => {
+            eprintln!("Puzzle solution not yet available");
+            Ok(None)
         }
     }
 }
 
-fn fix_calibration_line(s: &str) -> Result<usize> {
-    let mut iterator = s.chars().filter_map(|c| c.to_digit(10)).peekable();
-    let first_digit = iterator.peek().ok_or(anyhow!("No first digit"))?.clone();
-    let last_digit = iterator.last().ok_or(anyhow!("No last digit"))?;
-    Ok((first_digit * 10 + last_digit).try_into()?)
-}
-
-fn fix_calibration_line_with_string_digits(s: &str) -> Result<usize> {
-    let first_digit_regex = Regex::new("one|two|three|four|five|six|seven|eight|nine|[0-9]")?;
-    let last_digit_regex =
-        Regex::new("(?:.*)(one|two|three|four|five|six|seven|eight|nine|[0-9])")?;
-    let first_digit = to_digit_incl_text(
-        first_digit_regex
-            .find(s)
-            .ok_or(anyhow!("No first digit"))?
-            .as_str(),
-    )?;
-    let last_digit = to_digit_incl_text(
-        last_digit_regex
-            .captures(s)
-            .ok_or(anyhow!("No last digit"))?
-            .get(1)
-            .ok_or(anyhow!("No last digit"))?
-            .as_str(),
-    )?;
-    Ok((first_digit * 10 + last_digit).try_into()?)
+/// Parses each non-empty line with `parse_line`, noting which line failed.
+fn parse_lines<T>(text: &str, parse_line: impl Fn(&str) -> Result<T>) -> Result<Vec<T>> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, s)| !s.is_empty())
+        .map(|(i, s)| {
+            parse_line(s).with_context(|| format!("Failed to parse line {}: {:?}", i + 1, s))
+        })
+        .collect()
 }
 
-fn to_digit_incl_text(s: &str) -> Result<usize> {
-    match s {
-        "0" => Ok(0),
-        "one" | "1" => Ok(1),
-        "two" | "2" => Ok(2),
-        "three" | "3" => Ok(3),
-        "four" | "4" => Ok(4),
-        "five" | "5" => Ok(5),
-        "six" | "6" => Ok(6),
-        "seven" | "7" => Ok(7),
-        "eight" | "8" => Ok(8),
-        "nine" | "9" => Ok(9),
-        _ => Err(anyhow!("Invalid digit")),
-    }
-}
-
-fn parse_input<T: std::str::FromStr, IS>(input_strings: IS) -> impl Iterator<Item = T>
+fn parse_input<T>(text: &str) -> Result<Vec<T>>
 where
-    IS: Iterator<Item = String>,
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
 {
-    input_strings.filter_map(|s| s.parse::<T>().ok())
-}
-
-fn binary_from_input(input_strings: impl Iterator<Item = String>) -> impl Iterator<Item = usize> {
-    input_strings.filter_map(|s| usize::from_str_radix(&s, 2).ok())
+    parse_lines(text, |s| Ok(s.parse::<T>()?))
 }
 
-fn nonempty_input_lines(
-    input_strings: impl Iterator<Item = String>,
-) -> impl Iterator<Item = String> {
-    input_strings.filter(|s| !s.is_empty())
+fn binary_from_input(text: &str) -> Result<Vec<usize>> {
+    parse_lines(text, |s| Ok(usize::from_str_radix(s, 2)?))
 }
 
-pub fn file_lines_as_strings(input_path: &Path) -> impl Iterator<Item = String> {
-    let file = File::open(input_path).unwrap();
-    BufReader::new(file).lines().filter_map(|l| l.ok())
-}
-
-fn single_line_from_input(mut input_strings: impl Iterator<Item = String>) -> String {
-    input_strings.next().unwrap()
+fn single_line_from_input(input: &Input) -> Result<&str> {
+    input.lines().next().ok_or(anyhow!("Input is empty"))
 }
 
 pub fn cargo_input_file_path(year: usize, day: usize) -> PathBuf {
-    let base_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(Path::new("inputs"));
-    let filename = format!("input-{:04}-day{:02}", year, day);
-    Path::new(&base_dir).join(Path::new(&filename))
+    input_file_path(&cargo_input_dir(), year, day)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use test_case::test_case;
-
-    fn run_solution_for_test(year: usize, day: usize, puzzle: usize) -> usize {
-        let input_lines = file_lines_as_strings(&cargo_input_file_path(year, day));
-        run_solution(year, day, puzzle, input_lines).unwrap()
-    }
-
-    fn run_solution_for_example(year: usize, day: usize, puzzle: usize) -> usize {
-        let input_lines = example_input(year, day, puzzle)
-            .lines()
-            .map(|s| s.to_string());
-        run_solution(year, day, puzzle, input_lines).unwrap()
-    }
-
-    fn example_input(year: usize, day: usize, puzzle: usize) -> &'static str {
-        match (year, day, puzzle) {
-            (2022, 1, _) => {
-                "\
-1000
-2000
-3000
-
-4000
-
-5000
-6000
-
-7000
-8000
-9000
-
-10000"
-            }
-            (2022, 2, _) => {
-                "\
-A Y
-B X
-C Z"
-            }
-            (2023, 1, 1) => {
-                "\
-1abc2
-pqr3stu8vwx
-a1b2c3d4e5f
-treb7uchet"
-            }
-            (2023, 1, 2) => {
-                "\
-two1nine
-eightwothree
-abcone2threexyz
-xtwone3four
-4nineeightseven2
-zoneight234
-7pqrstsixteen"
-            }
-            // Add next example above this line.
-            _ => "",
-        }
-    }
-
-    #[test_case(2023, 1, 1, 142)]
-    #[test_case(2023, 1, 2, 281)]
-    fn check_examples(year: usize, day: usize, puzzle: usize, result: usize) {
-        assert_eq!(run_solution_for_example(year, day, puzzle), result)
-    }
-
-    #[test_case(2022, 1, 1, 24000)]
-    // Test case fails: #[test_case(2022, 1, 2, 45000)]
-    #[test_case(2022, 2, 2, 12)]
-    fn check_examples_old(year: usize, day: usize, puzzle: usize, result: usize) {
-        assert_eq!(run_solution_for_example(year, day, puzzle), result)
-    }
-
-    #[test_case(2021, 1, 1, 1466)]
-    #[test_case(2021, 1, 2, 1491)]
-    #[test_case(2021, 2, 2, 1947878632)]
-    #[test_case(2021, 3, 1, 4006064)]
-    #[test_case(2021, 3, 2, 5941884)]
-    #[test_case(2021, 4, 1, 2496)]
-    #[test_case(2021, 4, 2, 25925)]
-    #[test_case(2021, 5, 1, 5084)]
-    #[test_case(2021, 5, 2, 17882)]
-    #[test_case(2021, 6, 1, 352195)]
-    #[test_case(2021, 6, 2, 1600306001288)]
-    #[test_case(2021, 7, 2, 92881128)]
-    #[test_case(2021, 8, 1, 397)]
-    #[test_case(2021, 8, 2, 1027422)]
-    #[test_case(2022, 1, 1, 71506)]
-    #[test_case(2022, 1, 2, 209603)]
-    #[ignore]
-    fn check_solutions_old(year: usize, day: usize, puzzle: usize, result: usize) {
-        assert_eq!(run_solution_for_test(year, day, puzzle), result)
-    }
+/// The inputs directory in the crate's source tree.
+pub fn cargo_input_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs")
+}
 
-    #[test_case(2023, 1, 1, 57346)]
-    #[test_case(2023, 1, 2, 57345)]
-    fn check_solutions(year: usize, day: usize, puzzle: usize, result: usize) {
-        assert_eq!(run_solution_for_test(year, day, puzzle), result)
-    }
+/// Where the input for the day is expected in `input_dir`.
+pub fn input_file_path(input_dir: &Path, year: usize, day: usize) -> PathBuf {
+    input_dir.join(format!("input-{:04}-day{:02}", year, day))
 }
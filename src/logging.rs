@@ -0,0 +1,135 @@
+use anyhow::{anyhow, Result};
+use log::{LevelFilter, Log, Metadata, Record};
+
+/// Which log levels to show for which modules.  A filter is a comma-separated list of
+/// `MODULE=LEVEL` directives, plus at most one bare `LEVEL` that applies everywhere
+/// else, for example `debug,aoc::old::coordinates=trace`.  The most specific module
+/// wins.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Filter {
+    default: LevelFilter,
+    /// (module path, level) pairs, most specific first.
+    modules: Vec<(String, LevelFilter)>,
+}
+
+impl Filter {
+    /// The level shown for `-v` repeated `verbosity` times: warnings only by default,
+    /// then debug, then trace.
+    pub fn from_verbosity(verbosity: u8) -> Filter {
+        let default = match verbosity {
+            0 => LevelFilter::Warn,
+            1 => LevelFilter::Debug,
+            _ => LevelFilter::Trace,
+        };
+        Filter {
+            default,
+            modules: Vec::new(),
+        }
+    }
+
+    /// Adds the directives in `spec` to this filter.
+    pub fn parse(mut self, spec: &str) -> Result<Filter> {
+        for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            let (module, level) = match directive.split_once('=') {
+                Some((module, level)) => (Some(module.trim()), level.trim()),
+                None => (None, directive),
+            };
+            let level = level
+                .parse::<LevelFilter>()
+                .map_err(|_| anyhow!("Unknown log level '{}' in '{}'", level, directive))?;
+            match module {
+                Some(module) => self.modules.push((module.to_string(), level)),
+                None => self.default = level,
+            }
+        }
+        self.modules
+            .sort_by_key(|(module, _)| std::cmp::Reverse(module.len()));
+        Ok(self)
+    }
+
+    /// The most verbose level shown for messages from the `target` module.
+    pub fn level_for(&self, target: &str) -> LevelFilter {
+        self.modules
+            .iter()
+            .find(|(module, _)| is_within(target, module))
+            .map_or(self.default, |&(_, level)| level)
+    }
+
+    /// The most verbose level shown anywhere.
+    pub fn max_level(&self) -> LevelFilter {
+        self.modules
+            .iter()
+            .map(|&(_, level)| level)
+            .fold(self.default, Ord::max)
+    }
+}
+
+/// Whether `target` is `module` or one of its submodules.
+fn is_within(target: &str, module: &str) -> bool {
+    target
+        .strip_prefix(module)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+}
+
+struct Logger(Filter);
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.0.level_for(metadata.target())
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("[{} {}] {}", record.level(), record.target(), record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Sends log messages allowed by `filter` to stderr.  Messages at levels that no module
+/// shows are skipped before their arguments are even formatted.
+pub fn init(filter: Filter) -> Result<()> {
+    log::set_max_level(filter.max_level());
+    log::set_boxed_logger(Box::new(Logger(filter)))
+        .map_err(|_| anyhow!("A logger has already been set"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_verbosity() {
+        assert_eq!(Filter::from_verbosity(0).max_level(), LevelFilter::Warn);
+        assert_eq!(Filter::from_verbosity(1).max_level(), LevelFilter::Debug);
+        assert_eq!(Filter::from_verbosity(3).max_level(), LevelFilter::Trace);
+    }
+
+    #[test]
+    fn check_module_filters() {
+        let filter = Filter::from_verbosity(0)
+            .parse("aoc::old=debug, aoc::old::coordinates=trace,aoc::y2021=off")
+            .unwrap();
+        assert_eq!(filter.level_for("aoc::old::position"), LevelFilter::Debug);
+        assert_eq!(
+            filter.level_for("aoc::old::coordinates"),
+            LevelFilter::Trace
+        );
+        assert_eq!(filter.level_for("aoc::y2021::day05"), LevelFilter::Off);
+        assert_eq!(filter.level_for("aoc::y2022"), LevelFilter::Warn);
+        assert_eq!(filter.level_for("aoc::oldish"), LevelFilter::Warn);
+        assert_eq!(filter.max_level(), LevelFilter::Trace);
+    }
+
+    #[test]
+    fn check_default_level() {
+        let filter = Filter::from_verbosity(2).parse("info").unwrap();
+        assert_eq!(filter.level_for("aoc::runner"), LevelFilter::Info);
+    }
+
+    #[test]
+    fn check_bad_level() {
+        assert!(Filter::from_verbosity(0).parse("aoc=loud").is_err());
+    }
+}
@@ -1,38 +1,862 @@
-#![allow(deprecated)]
-use aoc::{cargo_input_file_path, file_lines_as_strings, run_solution};
-use clap::{load_yaml, App};
-use std::path::PathBuf;
-use std::time::Instant;
+mod cli;
+
+use crate::cli::{
+    BenchArgs, Cli, Command, CompareArgs, DayArgs, ExtractArgs, FetchArgs, Format, ListArgs,
+    NewArgs, RunArgs, SubmitArgs, VerifyArgs, WatchArgs,
+};
+use anyhow::{anyhow, Context, Result};
+use aoc::alloc_stats::AllocStats;
+use aoc::bench::{self, Stats};
+use aoc::calendar::{default_year_and_day, SystemClock};
+use aoc::client::{self, Client};
+use aoc::config::Config;
+use aoc::cross_check;
+use aoc::examples;
+use aoc::history;
+use aoc::known_answers::{self, Verdict};
+use aoc::logging::{self, Filter};
+use aoc::progress;
+use aoc::puzzle_page;
+use aoc::runner::{self, Status, TimedOut};
+use aoc::scaffold::{self, Scaffolded};
+use aoc::submit;
+use aoc::watch::{self, ExampleCheck, Outcome, Snapshot};
+use aoc::{
+    describe_missing_solution, find_solution, input_file_path, registry, BadInput, DayRun, Input,
+    InputSource, PartRun, Progress, Registration,
+};
+use clap::{CommandFactory, Parser};
+use itertools::Itertools;
+use std::cmp;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Exit code when a solution returned an error.
+const EXIT_FAILED: i32 = 1;
+/// Exit code when there is no answer for (at least one of) the puzzles yet.
+const EXIT_UNSOLVED: i32 = 2;
+/// Exit code when the puzzle input could not be read or parsed.
+const EXIT_BAD_INPUT: i32 = 3;
 
 fn main() {
-    let yaml = load_yaml!("cli.yaml");
-    let matches = App::from_yaml(yaml).get_matches();
+    let cli = Cli::parse();
+    init_logging(cli.verbose, cli.log.as_deref());
+    match cli.command {
+        Command::Run(args) => {
+            if args.all_years {
+                run_bulk(&args, None)
+            } else if args.all {
+                run_bulk(&args, args.puzzle.year)
+            } else if let Some(dir) = &args.inputs_dir {
+                run_inputs(&args, dir)
+            } else if args.format == Format::Json {
+                run_one_json(&args)
+            } else {
+                run_one(&args)
+            }
+        }
+        Command::Bench(args) => bench(&args),
+        Command::Compare(args) => compare(&args),
+        Command::Verify(args) => verify(&args),
+        Command::New(args) => new(&args),
+        Command::Extract(args) => extract(&args),
+        Command::Fetch(args) => fetch(&args),
+        Command::Submit(args) => submit(&args),
+        Command::List(args) => list(&args),
+        Command::Watch(args) => watch(&args),
+        Command::Completions { shell } => {
+            clap_complete::generate(shell, &mut Cli::command(), "aoc", &mut io::stdout())
+        }
+    }
+}
 
-    let year: usize = matches.value_of_t("year").unwrap_or(2022);
-    let day: usize = matches.value_of_t("DAY").unwrap_or(1);
-    let puzzle: usize = matches.value_of_t("PUZZLE").unwrap_or(1);
-    println!(
+fn run_one(run_args: &RunArgs) {
+    let args = &run_args.puzzle;
+    let (year, day) = default_year_and_day(&SystemClock, registry(), args.year, args.day);
+    let puzzles = args.puzzles();
+    eprintln!(
         "Running day {}, puzzle {} for Advent of Code {}",
-        day, puzzle, year
+        day,
+        puzzles.iter().join(" and "),
+        year
     );
 
-    let input_path = match matches.value_of("input") {
-        Some(input) => PathBuf::from(input),
-        None => cargo_input_file_path(year, day),
+    let source = InputSource::resolve(args.input.as_deref(), &load_config().input_dir(), year, day);
+
+    let (registration, input) = match load(year, day, &source) {
+        Ok(Some(loaded)) => loaded,
+        Ok(None) => process::exit(EXIT_UNSOLVED),
+        Err(err) => exit_with_error(err),
     };
+    let day_run = match runner::run_with_deadlines(
+        registration,
+        &puzzles,
+        Arc::new(input),
+        run_args.timeout(),
+    ) {
+        Ok(day_run) => day_run,
+        Err(err) => exit_with_error(err),
+    };
+    let timed_out = |part: &PartRun| matches!(&part.answer, Err(err) if err.is::<TimedOut>());
+    // If every part gave up, the parse may not have finished either.
+    if !day_run.parts.iter().all(timed_out) {
+        println!(
+            "Parsed input in {} microseconds{}",
+            day_run.parse_duration.as_micros(),
+            describe_allocations(day_run.parse_allocations)
+        );
+        println!();
+    }
 
+    let mut exit_code = 0;
+    for part in day_run.parts {
+        let duration = part.duration.as_micros();
+        let allocations = describe_allocations(part.allocations);
+        match part.answer {
+            Ok(Some(a)) => {
+                if a.is_multiline() {
+                    println!(
+                        "Puzzle {} ANSWER in {} microseconds{}:\n{}",
+                        part.puzzle, duration, allocations, a
+                    )
+                } else {
+                    println!(
+                        "Puzzle {} ANSWER: {} in {} microseconds{}",
+                        part.puzzle, a, duration, allocations
+                    )
+                }
+            }
+            Ok(None) => {
+                println!("{}", describe_no_answer(year, day, part.puzzle));
+                exit_code = cmp::max(exit_code, EXIT_UNSOLVED);
+            }
+            Err(err) if err.is::<TimedOut>() => {
+                eprintln!("{}", err);
+                exit_code = EXIT_FAILED;
+            }
+            Err(err) => {
+                eprintln!("Error: {:?}", err);
+                exit_code = EXIT_FAILED;
+            }
+        }
+    }
+    process::exit(exit_code)
+}
+
+/// Why a part gave no answer, going by the progress its solution records.
+fn describe_no_answer(year: usize, day: usize, puzzle: usize) -> String {
+    let progress = find_solution(year, day)
+        .and_then(|registration| registration.progress.get(puzzle.wrapping_sub(1)).copied());
+    match progress {
+        Some(Progress::NoLongerAvailable) => format!(
+            "The answer for day {}, puzzle {} of {} is no longer available",
+            day, puzzle, year
+        ),
+        Some(Progress::Stubbed) => format!(
+            "Day {}, puzzle {} of {} hasn't been solved yet",
+            day, puzzle, year
+        ),
+        _ => format!("No answer for day {}, puzzle {} of {}", day, puzzle, year),
+    }
+}
+
+/// What was allocated, to follow a timing, if allocations were counted.
+fn describe_allocations(stats: Option<AllocStats>) -> String {
+    stats.map_or_else(String::new, |stats| format!(" ({})", stats))
+}
+
+/// Like `run_one`, but prints a JSON object per part and nothing else on stdout.
+fn run_one_json(run_args: &RunArgs) {
+    let args = &run_args.puzzle;
+    let (year, day) = default_year_and_day(&SystemClock, registry(), args.year, args.day);
+    let Some(registration) = find_solution(year, day) else {
+        eprintln!("{}", describe_missing_solution(registry(), year, day));
+        process::exit(EXIT_UNSOLVED)
+    };
+    let source = InputSource::resolve(args.input.as_deref(), &load_config().input_dir(), year, day);
+    eprintln!("Reading input from {}", source);
+
+    let reports = runner::run_day(registration, &args.puzzles(), &source, run_args.timeout());
+    let mut exit_code = 0;
+    for report in &reports {
+        println!("{}", report.to_json());
+        match &report.status {
+            Status::Ok => {}
+            Status::Unsolved => {
+                eprintln!("{}", describe_no_answer(year, day, report.puzzle));
+                exit_code = cmp::max(exit_code, EXIT_UNSOLVED)
+            }
+            Status::MissingInput => exit_code = EXIT_BAD_INPUT,
+            Status::TimedOut => {
+                eprintln!(
+                    "Gave up on puzzle {} after {} seconds",
+                    report.puzzle, run_args.timeout
+                );
+                exit_code = EXIT_FAILED;
+            }
+            Status::Error(err) => {
+                eprintln!("Error: {}", err);
+                exit_code = EXIT_FAILED;
+            }
+        }
+    }
+    process::exit(exit_code)
+}
+
+/// Runs every registered day whose input exists, for one year or all of them, and
+/// prints a summary table, or a JSON object per part.  Exits non-zero only if a
+/// solution failed or timed out.
+fn run_bulk(run_args: &RunArgs, year: Option<usize>) {
+    let args = &run_args.puzzle;
+    let input_dir = match args.input.as_deref() {
+        Some(input) if Path::new(input).is_dir() => PathBuf::from(input),
+        Some(input) => exit_with_error(anyhow!(
+            "--input must be a directory when running more than one day, not {}",
+            input
+        )),
+        None => load_config().input_dir(),
+    };
+    match year {
+        Some(year) => eprintln!("Running every registered day of Advent of Code {}", year),
+        None => eprintln!("Running every registered day of Advent of Code"),
+    }
+    eprintln!("Reading inputs from {}", input_dir.display());
     let now = Instant::now();
-    let answer = if input_path.is_file() {
-        let input_lines = file_lines_as_strings(&input_path);
-        run_solution(year, day, puzzle, input_lines)
+    if run_args.jobs.get() > 1 {
+        eprintln!("Running {} parts at a time", run_args.jobs);
+    }
+    let reports = runner::run_registered(year, &input_dir, run_args.timeout(), run_args.jobs);
+    let wall_clock = now.elapsed();
+
+    match run_args.format {
+        Format::Text => {
+            println!();
+            println!("{}", runner::format_table(&reports));
+            println!();
+        }
+        Format::Json => {
+            for report in &reports {
+                println!("{}", report.to_json());
+            }
+        }
+    }
+
+    let failures = reports
+        .iter()
+        .filter_map(|r| match &r.status {
+            Status::Error(err) => Some((r, err.clone())),
+            Status::TimedOut => Some((r, format!("gave up after {} seconds", run_args.timeout))),
+            _ => None,
+        })
+        .collect_vec();
+    for (r, err) in &failures {
+        eprintln!("Day {}, puzzle {} of {}: {}", r.day, r.puzzle, r.year, err);
+    }
+    eprintln!(
+        "Total runtime: {} microseconds ({} microseconds wall clock)",
+        runner::total_duration(&reports).as_micros(),
+        wall_clock.as_micros()
+    );
+    if !failures.is_empty() {
+        process::exit(EXIT_FAILED)
+    }
+}
+
+/// Runs one day against every input in `dir`, checking the answers given alongside
+/// them, and shows how the timings vary.  Exits with `EXIT_FAILED` if any answer
+/// disagrees or a run fails.
+fn run_inputs(run_args: &RunArgs, dir: &Path) {
+    let args = &run_args.puzzle;
+    let (year, day) = default_year_and_day(&SystemClock, registry(), args.year, args.day);
+    let puzzles = args.puzzles();
+    let Some(registration) = find_solution(year, day) else {
+        eprintln!("{}", describe_missing_solution(registry(), year, day));
+        process::exit(EXIT_UNSOLVED)
+    };
+    let inputs = match cross_check::discover(dir, year, day) {
+        Ok(inputs) if inputs.is_empty() => {
+            exit_with_error(anyhow!("No input files in {}", dir.display()))
+        }
+        Ok(inputs) => inputs,
+        Err(err) => exit_with_error(err),
+    };
+    eprintln!(
+        "Running day {}, puzzle {} for Advent of Code {} against {} inputs in {}",
+        day,
+        puzzles.iter().join(" and "),
+        year,
+        inputs.len(),
+        dir.display()
+    );
+
+    let checks = cross_check::run(registration, &puzzles, &inputs, run_args.timeout());
+    match run_args.format {
+        Format::Text => {
+            println!();
+            println!("{}", cross_check::format_results(&checks));
+            println!();
+            println!("{}", cross_check::format_spread(&checks));
+        }
+        Format::Json => {
+            for check in &checks {
+                println!("{}", check.to_json());
+            }
+        }
+    }
+    for check in checks.iter().filter(|c| c.is_failure()) {
+        if let Status::Error(err) = &check.report.status {
+            eprintln!("{}, puzzle {}: {}", check.name, check.report.puzzle, err);
+        }
+    }
+    let failures = checks.iter().filter(|c| c.is_failure()).count();
+    if failures > 0 {
+        eprintln!();
+        eprintln!(
+            "{} of {} runs failed or disagreed with the expected answer",
+            failures,
+            checks.len()
+        );
+        process::exit(EXIT_FAILED)
+    }
+}
+
+/// Times parse and solve separately over many runs, prints the statistics and writes
+/// them as JSON.
+fn bench(bench_args: &BenchArgs) {
+    let args = &bench_args.puzzle;
+    let (year, day) = default_year_and_day(&SystemClock, registry(), args.year, args.day);
+    let puzzles = args.puzzles();
+    let (runs, warmup, output) = (bench_args.runs, bench_args.warmup, &bench_args.output);
+
+    let source = InputSource::resolve(args.input.as_deref(), &load_config().input_dir(), year, day);
+    let (registration, input) = match load(year, day, &source) {
+        Ok(Some(loaded)) => loaded,
+        Ok(None) => process::exit(EXIT_UNSOLVED),
+        Err(err) => exit_with_error(err),
+    };
+    println!(
+        "Benchmarking day {}, puzzle {} for Advent of Code {} ({} runs after {} warm-up)",
+        day,
+        puzzles.iter().join(" and "),
+        year,
+        runs,
+        warmup
+    );
+    let report = match bench::bench_day(registration, &puzzles, &input, warmup, runs) {
+        Ok(report) => report,
+        Err(err) => exit_with_error(err),
+    };
+
+    println!();
+    println!(
+        "{:<8} {:>12} {:>12} {:>12} {:>12}",
+        "", "min µs", "median µs", "mean µs", "stddev µs"
+    );
+    print_stats("Parse", &report.parse);
+    for part in &report.parts {
+        print_stats(&format!("Puzzle {}", part.puzzle), &part.solve);
+    }
+
+    let json = serde_json::to_string_pretty(&report).expect("Bench reports always serialize");
+    if let Err(err) = fs::write(output, json + "\n")
+        .with_context(|| format!("Failed to write {}", output.display()))
+    {
+        exit_with_error(err)
+    }
+    println!();
+    println!("Wrote {}", output.display());
+
+    let recorded_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs());
+    let entries = history::entries_from_report(&report, &history::current_commit(), recorded_at);
+    if let Err(err) = history::append(&bench_args.history, &entries) {
+        exit_with_error(err)
+    }
+    println!("Recorded in {}", bench_args.history.display());
+}
+
+/// Compares the latest benchmark of each day's parse and each part with earlier ones,
+/// exiting with `EXIT_FAILED` if any got slower.
+fn compare(args: &CompareArgs) {
+    let history_path = &args.history;
+    let threshold = args.threshold;
+    let entries = match history::load(history_path) {
+        Ok(entries) => entries,
+        Err(err) => exit_with_error(err),
+    };
+    if entries.is_empty() {
+        println!(
+            "No benchmark history in {}; run `aoc bench` first",
+            history_path.display()
+        );
+        return;
+    }
+
+    let comparisons = history::compare(&entries, threshold / 100.0);
+    println!("{}", history::format_comparisons(&comparisons));
+    let regressions = comparisons.iter().filter(|c| c.is_regression()).count();
+    if regressions > 0 {
+        println!();
+        println!(
+            "{} timing(s) more than {}% slower than an earlier run",
+            regressions, threshold
+        );
+        process::exit(EXIT_FAILED)
+    }
+}
+
+fn print_stats(label: &str, stats: &Stats) {
+    let micros = |duration: Duration| duration.as_secs_f64() * 1e6;
+    println!(
+        "{:<8} {:>12.3} {:>12.3} {:>12.3} {:>12.3}",
+        label,
+        micros(stats.min),
+        micros(stats.median),
+        micros(stats.mean),
+        micros(stats.stddev)
+    );
+}
+
+/// Checks solutions against the known answers, exiting with `EXIT_FAILED` if any answer
+/// is wrong or a solution fails.  Parts without an input file are skipped.
+fn verify(args: &VerifyArgs) {
+    let (year, day) = (args.year, args.day);
+    let answers_path = match &args.answers {
+        Some(path) => path.clone(),
+        None => known_answers::answers_file_path(),
+    };
+    let known = match known_answers::load(&answers_path) {
+        Ok(known) => known,
+        Err(err) => exit_with_error(err),
+    };
+    let known = known
+        .into_iter()
+        .filter(|k| year.is_none_or(|year| k.year == year) && day.is_none_or(|day| k.day == day))
+        .collect_vec();
+
+    let input_dir = load_config().input_dir();
+    let verifications =
+        known_answers::verify(&known, |year, day| input_file_path(&input_dir, year, day));
+    for v in &verifications {
+        let expected = match v.verdict {
+            Verdict::Wrong(_) => format!(", expected {}", v.known.answer.single_line()),
+            _ => String::new(),
+        };
+        println!(
+            "{} day {:>2} part {}: {}{}",
+            v.known.year, v.known.day, v.known.part, v.verdict, expected
+        );
+    }
+    let count =
+        |wanted: fn(&Verdict) -> bool| verifications.iter().filter(|v| wanted(&v.verdict)).count();
+    let failed = count(|v| matches!(v, Verdict::Wrong(_) | Verdict::Error(_) | Verdict::Unsolved));
+    println!();
+    println!(
+        "{} correct, {} failed, {} skipped (missing input)",
+        count(|v| *v == Verdict::Correct),
+        failed,
+        count(|v| *v == Verdict::MissingInput)
+    );
+    if failed > 0 {
+        process::exit(EXIT_FAILED)
+    }
+}
+
+/// Scaffolds a new day: solution module, registration, and example and answer stubs.
+fn new(args: &NewArgs) {
+    let DayArgs { year, day } = args.day;
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+
+    if args.branch {
+        match scaffold::checkout_branch(root, year, day) {
+            Ok(branch) => println!("On branch {}", branch),
+            Err(err) => exit_with_error(err),
+        }
+    }
+    let scaffolded = match scaffold::new_day(root, year, day) {
+        Ok(scaffolded) => scaffolded,
+        Err(err) => exit_with_error(err),
+    };
+    for path in &scaffolded.created {
+        println!("Created {}", path.display());
+    }
+    for path in &scaffolded.updated {
+        println!("Updated {}", path.display());
+    }
+    if scaffolded == Scaffolded::default() {
+        println!("Day {} of {} is already set up", day, year);
+    }
+
+    println!();
+    println!(
+        "Put the examples and their answers in inputs/examples/{}-{:02}-<part>.txt and .answer",
+        year, day
+    );
+    println!("Run with 'cargo run run {} -y {}'", day, year);
+    println!(
+        "Once an answer is accepted, add it to answers.toml under [{}.{}]",
+        year, day
+    );
+}
+
+/// Lists the code blocks and highlighted answers in a saved puzzle page, and writes the
+/// likely example and answer for each part.
+fn extract(args: &ExtractArgs) {
+    let DayArgs { year, day } = args.day;
+    let page_path = &args.page;
+    let html = match fs::read_to_string(page_path)
+        .with_context(|| format!("Failed to read {}", page_path.display()))
+    {
+        Ok(html) => html,
+        Err(err) => exit_with_error(err),
+    };
+
+    let parts = puzzle_page::parse(&html);
+    if parts.is_empty() {
+        exit_with_error(anyhow!(
+            "No puzzle description found in {}",
+            page_path.display()
+        ))
+    }
+    for (index, part) in parts.iter().enumerate() {
+        println!("Part {}:", index + 1);
+        for (block, text) in part.code_blocks.iter().enumerate() {
+            println!("  Code block {}: {}", block + 1, puzzle_page::preview(text));
+        }
+        println!("  Highlighted: {}", part.answers.iter().join(", "));
+    }
+    if args.list {
+        return;
+    }
+
+    println!();
+    match puzzle_page::write_examples(&parts, &examples::examples_dir(), year, day) {
+        Ok(written) if written.is_empty() => println!("Example files already filled in"),
+        Ok(written) => {
+            for path in written {
+                println!("Wrote {}", path.display());
+            }
+        }
+        Err(err) => exit_with_error(err),
+    }
+}
+
+/// Downloads the day's input, unless it has already been downloaded.
+fn fetch(args: &FetchArgs) {
+    let DayArgs { year, day } = args.day;
+    let config = load_config();
+    let input_path = input_file_path(&config.input_dir(), year, day);
+
+    let client = match load_client(config, args.base_url.as_deref()) {
+        Ok(client) => client,
+        Err(err) => exit_with_error(err),
+    };
+    match client::fetch_input(&client, year, day, &input_path) {
+        Ok(true) => println!("Downloaded {}", input_path.display()),
+        Ok(false) => println!("Already have {}", input_path.display()),
+        Err(err) => exit_with_error(err),
+    }
+}
+
+/// Runs one part and submits its answer, unless earlier guesses show it is wrong.
+fn submit(args: &SubmitArgs) {
+    let DayArgs { year, day } = args.day;
+    let part = args.part;
+    let config = load_config();
+    let source = InputSource::File(input_file_path(&config.input_dir(), year, day));
+    let guesses_path = submit::guesses_file_path(&config.input_dir());
+
+    let day_run = match solve(year, day, &[part], &source) {
+        Ok(Some(day_run)) => day_run,
+        Ok(None) => process::exit(EXIT_UNSOLVED),
+        Err(err) => exit_with_error(err),
+    };
+    let answer = match day_run.parts.into_iter().next().map(|part| part.answer) {
+        Some(Ok(Some(answer))) => answer,
+        Some(Err(err)) => exit_with_error(err),
+        _ => {
+            println!("No answer for day {}, puzzle {} of {}", day, part, year);
+            process::exit(EXIT_UNSOLVED)
+        }
+    };
+    if answer.is_multiline() {
+        println!("{}", answer);
+        exit_with_error(anyhow!(
+            "Read the letters from the grid and submit them on the website"
+        ))
+    }
+    println!(
+        "Submitting {} for day {}, puzzle {} of {}",
+        answer, day, part, year
+    );
+
+    let client = match load_client(config, args.base_url.as_deref()) {
+        Ok(client) => client,
+        Err(err) => exit_with_error(err),
+    };
+    let submitted_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs());
+    let verdict = match submit::submit(
+        &client,
+        &guesses_path,
+        year,
+        day,
+        part,
+        &answer.to_string(),
+        submitted_at,
+    ) {
+        Ok(verdict) => verdict,
+        Err(err) => exit_with_error(err),
+    };
+    println!("The answer was {}", verdict);
+    if verdict == submit::Verdict::Correct {
+        println!(
+            "Add it to answers.toml: part{} = {} under [{}.{}]",
+            part, answer, year, day
+        );
     } else {
-        println!("Input file {} does not exist", input_path.display());
-        None
+        process::exit(EXIT_FAILED)
+    }
+}
+
+/// Prints a calendar of each year's progress, or a Markdown table of it.
+fn list(args: &ListArgs) {
+    let years = match args.year {
+        Some(year) => vec![year],
+        None => registry().map(|r| r.year).dedup().collect(),
+    };
+    let known = match known_answers::load(&known_answers::answers_file_path()) {
+        Ok(known) => known,
+        Err(err) => exit_with_error(err),
     };
-    let duration = now.elapsed().as_micros();
+    let history = match history::load(&args.history) {
+        Ok(history) => history,
+        Err(err) => exit_with_error(err),
+    };
+    let days = progress::collect(
+        registry(),
+        &years,
+        &known,
+        &history,
+        &load_config().input_dir(),
+    );
 
-    if let Some(a) = answer {
+    if args.markdown {
+        println!("{}", progress::format_markdown(&days));
+        return;
+    }
+    for year in years {
+        println!("{}", progress::format_calendar(year, &days));
         println!();
-        println!("ANSWER: {} in {} microseconds", a, duration)
     }
+    println!("{}", progress::LEGEND);
+}
+
+/// Re-runs the day's examples and then its input whenever an input, example or answer
+/// file changes.  A change to the source rebuilds the binary and restarts the watch
+/// with it.
+fn watch(args: &WatchArgs) {
+    let DayArgs { year, day } = args.day;
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let input_path = match &args.input {
+        Some(path) => path.clone(),
+        None => input_file_path(&load_config().input_dir(), year, day),
+    };
+    let examples_dir = examples::examples_dir();
+    let inputs = || {
+        let mut paths = watch::day_files(&examples_dir, year, day);
+        paths.push(input_path.clone());
+        Snapshot::take(paths)
+    };
+    let sources = || Snapshot::take(watch::source_files(&root.join("src")));
+
+    eprintln!(
+        "Watching day {} of {} (input {}); press Ctrl-C to stop",
+        day,
+        year,
+        input_path.display()
+    );
+    let (mut input_snapshot, mut source_snapshot) = (inputs(), sources());
+    run_watched(year, day, &input_path);
+    loop {
+        thread::sleep(Duration::from_millis(args.interval));
+
+        let snapshot = sources();
+        if !snapshot.changed_since(&source_snapshot).is_empty() {
+            eprintln!();
+            eprintln!("Source changed, rebuilding");
+            if let Err(err) = restart(root) {
+                eprintln!("Error: {:#}", err);
+            }
+            source_snapshot = snapshot;
+        }
+
+        let snapshot = inputs();
+        let changed = snapshot.changed_since(&input_snapshot);
+        if !changed.is_empty() {
+            eprintln!();
+            for path in changed {
+                eprintln!("Changed {}", path.display());
+            }
+            input_snapshot = snapshot;
+            run_watched(year, day, &input_path);
+        }
+    }
+}
+
+/// Checks the day's examples against their answers, then runs the real input.
+fn run_watched(year: usize, day: usize, input_path: &Path) {
+    let registration = match find_solution(year, day) {
+        Some(registration) => registration,
+        None => {
+            eprintln!("{}", describe_missing_solution(registry(), year, day));
+            return;
+        }
+    };
+    match watch::day_examples(&examples::examples_dir(), year, day) {
+        Ok(examples) if examples.is_empty() => println!("No examples for day {}", day),
+        Ok(examples) => {
+            let checks = examples.into_iter().map(ExampleCheck::run).collect_vec();
+            for check in &checks {
+                println!("{}", check);
+            }
+            let failed = checks
+                .iter()
+                .filter(|c| matches!(c.outcome, Outcome::Fail { .. } | Outcome::Error(_)))
+                .count();
+            println!("{} of {} examples failed", failed, checks.len());
+        }
+        Err(err) => eprintln!("Error: {:#}", err),
+    }
+
+    println!();
+    let source = InputSource::File(input_path.to_path_buf());
+    let reports = runner::run_day(registration, &[1, 2], &source, None);
+    println!("{}", runner::format_table(&reports));
+    for report in &reports {
+        if let Status::Error(err) = &report.status {
+            eprintln!("Puzzle {}: {}", report.puzzle, err);
+        }
+    }
+}
+
+/// Rebuilds the binary and replaces this process with the new one, run with the same
+/// arguments.  Only returns if that fails.
+fn restart(root: &Path) -> Result<()> {
+    let exe = env::current_exe().context("Failed to find the running binary")?;
+    let mut build = process::Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()));
+    build.args(["build", "--bin", "aoc"]).current_dir(root);
+    if exe
+        .parent()
+        .and_then(Path::file_name)
+        .is_some_and(|profile| profile == "release")
+    {
+        build.arg("--release");
+    }
+    if !build
+        .status()
+        .context("Failed to run cargo build")?
+        .success()
+    {
+        return Err(anyhow!("Build failed; still watching the previous build"));
+    }
+
+    let mut command = process::Command::new(exe);
+    command.args(env::args_os().skip(1));
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        Err(command.exec()).context("Failed to restart")
+    }
+    #[cfg(not(unix))]
+    {
+        let status = command.status().context("Failed to restart")?;
+        process::exit(status.code().unwrap_or(EXIT_FAILED))
+    }
+}
+
+fn init_logging(verbosity: u8, spec: Option<&str>) {
+    let filter = Filter::from_verbosity(verbosity);
+    let filter = match spec {
+        Some(spec) => filter.parse(spec),
+        None => Ok(filter),
+    };
+    if let Err(err) = filter.and_then(logging::init) {
+        exit_with_error(err)
+    }
+}
+
+fn load_config() -> Config {
+    match Config::load() {
+        Ok(config) => config,
+        Err(err) => exit_with_error(err),
+    }
+}
+
+/// A client for the configured site, or the one given with `--base-url`.
+fn load_client(mut config: Config, base_url: Option<&str>) -> Result<Client> {
+    if let Some(base_url) = base_url {
+        config.base_url = Some(base_url.to_string());
+    }
+    Client::from_config(&config)
+}
+
+fn exit_with_error(err: anyhow::Error) -> ! {
+    eprintln!("Error: {:?}", err);
+    process::exit(if err.is::<BadInput>() {
+        EXIT_BAD_INPUT
+    } else {
+        EXIT_FAILED
+    })
+}
+
+/// Parses the input once and runs each of `puzzles` against it, or returns `Ok(None)`
+/// if there is no solution registered for the day.
+fn solve(
+    year: usize,
+    day: usize,
+    puzzles: &[usize],
+    source: &InputSource,
+) -> Result<Option<DayRun>> {
+    match load(year, day, source)? {
+        Some((registration, input)) => registration.run_parts(puzzles, &input).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Finds the solution for the day and reads its input, or returns `Ok(None)` if there is
+/// no solution registered for the day.
+fn load(
+    year: usize,
+    day: usize,
+    source: &InputSource,
+) -> Result<Option<(&'static Registration, Input)>> {
+    let registration = match find_solution(year, day) {
+        Some(registration) => registration,
+        None => {
+            eprintln!("{}", describe_missing_solution(registry(), year, day));
+            return Ok(None);
+        }
+    };
+    if let InputSource::File(input_path) = source {
+        if !input_path.exists() {
+            return Err(anyhow!(
+                "Input file {} does not exist",
+                input_path.display()
+            ))
+            .context(registration.bad_input());
+        }
+    }
+    eprintln!("Reading input from {}", source);
+    // The whole input is read up front, so the parse timing doesn't include I/O.
+    let input = source.read().context(registration.bad_input())?;
+    Ok(Some((registration, input)))
 }
@@ -1,6 +1,8 @@
+use anyhow::{anyhow, Context, Result};
 use itertools::FoldWhile::{Continue, Done};
 use itertools::Itertools;
 
+#[derive(Clone)]
 pub struct BingoState {
     pub input_numbers: Vec<usize>,
     boards: Vec<BingoBoard>,
@@ -8,23 +10,28 @@ pub struct BingoState {
 }
 
 impl BingoState {
-    pub fn from_strs(
-        chunk_size: usize,
-        mut input_iter: impl Iterator<Item = String>,
-    ) -> BingoState {
-        let first_line = input_iter.next().unwrap();
-        BingoState {
-            input_numbers: first_line
-                .split(',')
-                .flat_map(|s| s.parse::<usize>())
-                .collect(),
-            boards: input_iter
-                .chunks(chunk_size)
-                .into_iter()
-                .map(|s| BingoBoard::from_strs(s.collect()))
-                .collect(),
+    /// Parses the called numbers from the first section, then one board per section.
+    pub fn from_sections<'a>(mut sections: impl Iterator<Item = &'a str>) -> Result<BingoState> {
+        let first_section = sections.next().ok_or(anyhow!("Missing bingo numbers"))?;
+        let input_numbers = first_section
+            .split(',')
+            .map(|s| {
+                s.parse::<usize>()
+                    .with_context(|| format!("Invalid bingo number {:?}", s))
+            })
+            .collect::<Result<_>>()?;
+        let boards = sections
+            .enumerate()
+            .map(|(i, s)| {
+                BingoBoard::from_str(s)
+                    .with_context(|| format!("Failed to parse bingo board {}", i + 1))
+            })
+            .collect::<Result<_>>()?;
+        Ok(BingoState {
+            input_numbers,
+            boards,
             last_number: 0,
-        }
+        })
     }
 
     pub fn run_until(self, predicate: fn(&BingoState) -> bool) -> BingoState {
@@ -76,6 +83,7 @@ struct Position {
     col: usize,
 }
 
+#[derive(Clone)]
 pub struct BingoBoard {
     height: usize,
     width: usize,
@@ -97,15 +105,30 @@ impl BingoBoard {
         }
     }
 
-    pub fn from_strs(strs: Vec<String>) -> BingoBoard {
-        let height = strs.len();
-        let width = strs[0].split_whitespace().count();
-        let board: Vec<usize> = strs
-            .iter()
-            .map(|v| v.split_whitespace().map(|s| s.parse::<usize>()).flatten())
-            .flatten()
-            .collect();
-        BingoBoard::new(height, width, board)
+    pub fn from_str(input_str: &str) -> Result<BingoBoard> {
+        let height = input_str.lines().count();
+        let width = input_str
+            .lines()
+            .next()
+            .ok_or(anyhow!("Empty bingo board"))?
+            .split_whitespace()
+            .count();
+        let board: Vec<usize> = input_str
+            .split_whitespace()
+            .map(|s| {
+                s.parse::<usize>()
+                    .with_context(|| format!("Invalid board number {:?}", s))
+            })
+            .collect::<Result<_>>()?;
+        if board.len() != height * width {
+            return Err(anyhow!(
+                "Expected a {}x{} board, found {} numbers",
+                height,
+                width,
+                board.len()
+            ));
+        }
+        Ok(BingoBoard::new(height, width, board))
     }
 
     fn locate_number(&self, number: usize) -> Option<Position> {
@@ -170,6 +193,17 @@ impl BingoBoard {
 mod tests {
     use super::*;
 
+    #[test]
+    fn check_from_sections() {
+        let state =
+            BingoState::from_sections(["7,4,9", "1 2\n3 4", " 5  6\n 7  8"].iter().copied())
+                .unwrap();
+        assert_eq!(state.input_numbers, vec![7, 4, 9]);
+        assert_eq!(state.boards.len(), 2);
+        assert_eq!(state.boards[1].sum_unmarked(), 26);
+        assert!(BingoState::from_sections(["1,2", "1 2\n3"].iter().copied()).is_err());
+    }
+
     #[test]
     fn check_bingo() {
         let board = vec![
@@ -1,34 +1,34 @@
-#[derive(PartialEq, Debug)]
+use anyhow::{anyhow, Context, Result};
+
+#[derive(PartialEq, Debug, Clone, Copy)]
 pub enum CommandMethod {
     Forward,
     Down,
     Up,
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone, Copy)]
 pub struct Command {
     pub method: CommandMethod,
     pub param: isize,
 }
 
 impl Command {
-    pub fn from_str(command_as_str: &str) -> Command {
-        let split: Vec<&str> = command_as_str.split_whitespace().collect();
-        match split[0] {
-            "forward" => Command {
-                method: CommandMethod::Forward,
-                param: split[1].parse::<isize>().unwrap(),
-            },
-            "down" => Command {
-                method: CommandMethod::Down,
-                param: split[1].parse::<isize>().unwrap(),
-            },
-            "up" => Command {
-                method: CommandMethod::Up,
-                param: split[1].parse::<isize>().unwrap(),
-            },
-            _ => panic!("Unknown command"),
-        }
+    pub fn from_str(command_as_str: &str) -> Result<Command> {
+        let mut split = command_as_str.split_whitespace();
+        let method = match split.next() {
+            Some("forward") => CommandMethod::Forward,
+            Some("down") => CommandMethod::Down,
+            Some("up") => CommandMethod::Up,
+            Some(unknown) => return Err(anyhow!("Unknown command {:?}", unknown)),
+            None => return Err(anyhow!("Missing command")),
+        };
+        let param = split
+            .next()
+            .ok_or(anyhow!("Missing command parameter"))?
+            .parse::<isize>()
+            .context("Invalid command parameter")?;
+        Ok(Command { method, param })
     }
 }
 
@@ -39,25 +39,33 @@ mod tests {
     #[test]
     fn check_commands_from_str() {
         assert_eq!(
-            Command::from_str("forward 5"),
+            Command::from_str("forward 5").unwrap(),
             Command {
                 method: CommandMethod::Forward,
                 param: 5,
             }
         );
         assert_eq!(
-            Command::from_str("down 8"),
+            Command::from_str("down 8").unwrap(),
             Command {
                 method: CommandMethod::Down,
                 param: 8,
             }
         );
         assert_eq!(
-            Command::from_str("up 3"),
+            Command::from_str("up 3").unwrap(),
             Command {
                 method: CommandMethod::Up,
                 param: 3,
             }
         );
     }
+
+    #[test]
+    fn check_invalid_commands() {
+        assert!(Command::from_str("sideways 5").is_err());
+        assert!(Command::from_str("forward").is_err());
+        assert!(Command::from_str("up three").is_err());
+        assert!(Command::from_str("").is_err());
+    }
 }
@@ -1,3 +1,4 @@
+use anyhow::{anyhow, Result};
 use itertools::Itertools;
 use std::cmp;
 use std::collections::HashMap;
@@ -26,12 +27,14 @@ impl Coords {
         Coords::xy(xy.0, xy.1)
     }
 
-    pub fn from_str(input_str: &str) -> Result<Coords, &str> {
+    pub fn from_str(input_str: &str) -> Result<Coords> {
         let tuple = input_str
             .split(',')
-            .filter_map(|s| s.parse::<isize>().ok())
+            .map(|s| s.trim().parse::<isize>())
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
             .collect_tuple()
-            .ok_or("Can't convert str to coords")?;
+            .ok_or_else(|| anyhow!("Can't convert {:?} to coords", input_str))?;
         Ok(Coords::from_cartesian_tuple(tuple))
     }
 }
@@ -50,12 +53,14 @@ impl LineSegment {
         }
     }
 
-    pub fn from_str(input_str: &str) -> Result<LineSegment, &str> {
+    pub fn from_str(input_str: &str) -> Result<LineSegment> {
         let tuple: (Coords, Coords) = input_str
             .split(" -> ")
-            .filter_map(|c| Coords::from_str(c).ok())
+            .map(Coords::from_str)
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
             .collect_tuple()
-            .ok_or("Can't parse string into LineSegment")?;
+            .ok_or_else(|| anyhow!("Can't parse {:?} into LineSegment", input_str))?;
         Ok(LineSegment::from_coords(tuple.0, tuple.1))
     }
 
@@ -109,7 +114,7 @@ impl LineSegment {
                 .map(|x| Coords::xy(x, y_start + (x - min_x) * y_step))
                 .collect()
         } else {
-            println!("Ignoring {:?}", self);
+            log::trace!("Ignoring {:?}", self);
             vec![]
         }
     }
@@ -163,6 +168,9 @@ mod tests {
             Coords::from_str("-12,0").unwrap().as_cartesian_tuple(),
             (-12, 0)
         );
+        assert!(Coords::from_str("1,2,3").is_err());
+        assert!(Coords::from_str("1,x").is_err());
+        assert!(LineSegment::from_str("1,1 -> ").is_err());
     }
 
     #[test]
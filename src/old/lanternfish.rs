@@ -1,5 +1,7 @@
+use anyhow::{Context, Result};
 use itertools::Itertools;
 
+#[derive(Clone)]
 pub struct LanternFish {
     birth_timer: usize,
     count: usize,
@@ -22,19 +24,24 @@ impl LanternFish {
     }
 }
 
+#[derive(Clone)]
 pub struct LanternShoal {
     fish: Vec<LanternFish>,
 }
 
 impl LanternShoal {
-    pub fn from_str(input_str: &str) -> LanternShoal {
-        LanternShoal {
+    pub fn from_str(input_str: &str) -> Result<LanternShoal> {
+        Ok(LanternShoal {
             fish: input_str
                 .split(',')
-                .flat_map(|s| s.parse::<usize>().ok())
-                .map(|i| LanternFish::new(i, 1))
-                .collect(),
-        }
+                .map(|s| {
+                    s.trim()
+                        .parse::<usize>()
+                        .map(|i| LanternFish::new(i, 1))
+                        .with_context(|| format!("Invalid birth timer {:?}", s))
+                })
+                .collect::<Result<_>>()?,
+        })
     }
 
     pub fn proceed_n_days(self, n: usize) -> LanternShoal {
@@ -73,19 +80,22 @@ mod tests {
     fn check_lantern_shoal() {
         assert_eq!(
             (0..18)
-                .fold(LanternShoal::from_str("3,4,3,1,2"), |ls, _| ls.next_day())
+                .fold(LanternShoal::from_str("3,4,3,1,2").unwrap(), |ls, _| ls
+                    .next_day())
                 .count(),
             26
         );
         assert_eq!(
             (0..80)
-                .fold(LanternShoal::from_str("3,4,3,1,2"), |ls, _| ls.next_day())
+                .fold(LanternShoal::from_str("3,4,3,1,2").unwrap(), |ls, _| ls
+                    .next_day())
                 .count(),
             5934
         );
         assert_eq!(
             (0..256)
-                .fold(LanternShoal::from_str("3,4,3,1,2"), |ls, _| ls.next_day())
+                .fold(LanternShoal::from_str("3,4,3,1,2").unwrap(), |ls, _| ls
+                    .next_day())
                 .count(),
             26984457539
         );
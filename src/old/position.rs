@@ -1,5 +1,3 @@
-use std::convert::TryInto;
-
 use crate::old::command::{Command, CommandMethod};
 
 #[derive(PartialEq, Debug)]
@@ -40,9 +38,9 @@ impl Position {
         Position::new(self.x, self.depth, self.aim + amount)
     }
 
-    pub fn multiply_x_by_depth(&self) -> usize {
-        println!("{:?}", self);
-        (self.x * self.depth).try_into().unwrap()
+    pub fn multiply_x_by_depth(&self) -> isize {
+        log::debug!("{:?}", self);
+        self.x * self.depth
     }
 }
 
@@ -54,12 +52,12 @@ mod tests {
     fn check_postion_change() {
         assert_eq!(
             Position::at_zero()
-                .exec_command(Command::from_str("forward 5"))
-                .exec_command(Command::from_str("down 5"))
-                .exec_command(Command::from_str("forward 8"))
-                .exec_command(Command::from_str("up 3"))
-                .exec_command(Command::from_str("down 8"))
-                .exec_command(Command::from_str("forward 2"))
+                .exec_command(Command::from_str("forward 5").unwrap())
+                .exec_command(Command::from_str("down 5").unwrap())
+                .exec_command(Command::from_str("forward 8").unwrap())
+                .exec_command(Command::from_str("up 3").unwrap())
+                .exec_command(Command::from_str("down 8").unwrap())
+                .exec_command(Command::from_str("forward 2").unwrap())
                 .multiply_x_by_depth(),
             900
         )
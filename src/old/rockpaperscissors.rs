@@ -1,3 +1,4 @@
+use anyhow::{anyhow, Result};
 use regex::Regex;
 
 #[derive(PartialEq)]
@@ -24,21 +25,21 @@ pub fn play_round(them: &Action, me: &Action) -> RoundResult {
     }
 }
 
-pub fn score_guide_round(input_str: &str) -> usize {
-    let re = Regex::new(r"([ABC]) ([XYZ])").unwrap();
-    let caps = re.captures(input_str).unwrap();
+pub fn score_guide_round(input_str: &str) -> Result<usize> {
+    let re = Regex::new(r"^([ABC]) ([XYZ])$")?;
+    let caps = re
+        .captures(input_str)
+        .ok_or(anyhow!("Expected \"<A|B|C> <X|Y|Z>\""))?;
 
-    let them = match caps.get(1).unwrap().as_str() {
+    let them = match &caps[1] {
         "A" => Action::Rock,
         "B" => Action::Paper,
-        "C" => Action::Scissors,
-        _ => panic!("Invalid input"),
+        _ => Action::Scissors,
     };
-    let required_result = match caps.get(2).unwrap().as_str() {
+    let required_result = match &caps[2] {
         "X" => RoundResult::Loss,
         "Y" => RoundResult::Draw,
-        "Z" => RoundResult::Win,
-        _ => panic!("Invalid input"),
+        _ => RoundResult::Win,
     };
     let me = if required_result == play_round(&them, &Action::Rock) {
         Action::Rock
@@ -53,12 +54,12 @@ pub fn score_guide_round(input_str: &str) -> usize {
         RoundResult::Draw => 3,
         RoundResult::Loss => 0,
     };
-    round_score
+    Ok(round_score
         + match me {
             Action::Rock => 1,
             Action::Paper => 2,
             Action::Scissors => 3,
-        }
+        })
 }
 
 #[cfg(test)]
@@ -95,4 +96,13 @@ mod tests {
             RoundResult::Draw
         );
     }
+
+    #[test]
+    fn test_score_guide_round() {
+        assert_eq!(score_guide_round("A Y").unwrap(), 4);
+        assert_eq!(score_guide_round("B X").unwrap(), 1);
+        assert_eq!(score_guide_round("C Z").unwrap(), 7);
+        assert!(score_guide_round("D Y").is_err());
+        assert!(score_guide_round("A").is_err());
+    }
 }
@@ -0,0 +1,285 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use itertools::Itertools;
+
+use crate::history::HistoryEntry;
+use crate::input_file_path;
+use crate::known_answers::KnownAnswer;
+use crate::solution::{Progress, Registration};
+
+/// How far one part has got.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartState {
+    /// There is no solution registered for the day.
+    Unregistered,
+    Stubbed,
+    NoLongerAvailable,
+    Solved,
+    /// Solved, and the answer is in the known answers.
+    Verified,
+}
+
+impl PartState {
+    fn symbol(self) -> char {
+        match self {
+            PartState::Unregistered => ' ',
+            PartState::Stubbed => '.',
+            PartState::NoLongerAvailable => 'x',
+            PartState::Solved => '+',
+            PartState::Verified => '*',
+        }
+    }
+
+    fn markdown(self) -> &'static str {
+        match self {
+            PartState::Unregistered => "",
+            PartState::Stubbed => "stub",
+            PartState::NoLongerAvailable => "no longer available",
+            PartState::Solved => "solved",
+            PartState::Verified => "⭐",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayProgress {
+    pub year: usize,
+    pub day: usize,
+    pub parts: [PartState; 2],
+    pub has_input: bool,
+    /// The median time to parse the input and run both parts, from the latest
+    /// benchmark of each part.
+    pub last_bench_us: Option<f64>,
+}
+
+impl DayProgress {
+    pub fn is_registered(&self) -> bool {
+        self.parts[0] != PartState::Unregistered
+    }
+}
+
+/// The progress on all 25 days of each of `years`, given the solutions in
+/// `registrations`.
+pub fn collect<'a>(
+    registrations: impl IntoIterator<Item = &'a Registration>,
+    years: &[usize],
+    known: &[KnownAnswer],
+    history: &[HistoryEntry],
+    input_dir: &Path,
+) -> Vec<DayProgress> {
+    // Later entries replace earlier ones, leaving the latest benchmark of each part.
+    let latest: BTreeMap<_, _> = history
+        .iter()
+        .map(|entry| ((entry.year, entry.day, entry.part), entry))
+        .collect();
+    let registered: BTreeMap<_, _> = registrations
+        .into_iter()
+        .map(|r| ((r.year, r.day), r.progress))
+        .collect();
+
+    years
+        .iter()
+        .cartesian_product(1..=25)
+        .map(|(&year, day)| {
+            let part_state = |part: usize| match registered.get(&(year, day)) {
+                None => PartState::Unregistered,
+                Some(progress) => match progress[part - 1] {
+                    Progress::Stubbed => PartState::Stubbed,
+                    Progress::NoLongerAvailable => PartState::NoLongerAvailable,
+                    Progress::Solved
+                        if known
+                            .iter()
+                            .any(|k| (k.year, k.day, k.part) == (year, day, part)) =>
+                    {
+                        PartState::Verified
+                    }
+                    Progress::Solved => PartState::Solved,
+                },
+            };
+            let benches = (1..=2)
+                .filter_map(|part| latest.get(&(year, day, part)))
+                .collect_vec();
+            let last_bench_us = (!benches.is_empty()).then(|| {
+                let parse = benches
+                    .iter()
+                    .map(|entry| entry.parse_median_us)
+                    .fold(0.0, f64::max);
+                parse
+                    + benches
+                        .iter()
+                        .map(|entry| entry.solve_median_us)
+                        .sum::<f64>()
+            });
+            DayProgress {
+                year,
+                day,
+                parts: [part_state(1), part_state(2)],
+                has_input: input_file_path(input_dir, year, day).exists(),
+                last_bench_us,
+            }
+        })
+        .collect()
+}
+
+/// A duration in microseconds, in whichever unit keeps it short.
+fn format_micros(micros: f64) -> String {
+    if micros < 1e3 {
+        format!("{:.0}µs", micros)
+    } else if micros < 1e6 {
+        format!("{:.1}ms", micros / 1e3)
+    } else {
+        format!("{:.2}s", micros / 1e6)
+    }
+}
+
+/// Renders one year as a five-by-five grid of days, each showing its parts' states,
+/// whether the input has been downloaded, and the last benchmark time.
+pub fn format_calendar(year: usize, days: &[DayProgress]) -> String {
+    let cells = days
+        .iter()
+        .filter(|d| d.year == year)
+        .map(|d| {
+            format!(
+                "{:>2} {}{} {} {:>7}",
+                d.day,
+                d.parts[0].symbol(),
+                d.parts[1].symbol(),
+                if d.has_input { 'i' } else { '-' },
+                d.last_bench_us.map(format_micros).unwrap_or_default()
+            )
+        })
+        .collect_vec();
+    let count = |state| {
+        days.iter()
+            .filter(|d| d.year == year)
+            .flat_map(|d| d.parts)
+            .filter(|&part| part == state)
+            .count()
+    };
+
+    let mut lines = vec![format!("Advent of Code {}", year)];
+    lines.extend(
+        cells
+            .chunks(5)
+            .map(|row| row.join(" | ").trim_end().to_string()),
+    );
+    lines.push(format!(
+        "{} verified, {} solved, {} stubbed, {} no longer available",
+        count(PartState::Verified),
+        count(PartState::Solved),
+        count(PartState::Stubbed),
+        count(PartState::NoLongerAvailable)
+    ));
+    lines.join("\n")
+}
+
+/// Explains the symbols in `format_calendar`.
+pub const LEGEND: &str =
+    "* verified  + solved  . stubbed  x no longer available  i input downloaded";
+
+/// Renders the registered days as a Markdown table, for a README.
+pub fn format_markdown(days: &[DayProgress]) -> String {
+    let mut lines = vec![
+        "| Year | Day | Part 1 | Part 2 | Input | Last bench |".to_string(),
+        "|------|-----|--------|--------|-------|------------|".to_string(),
+    ];
+    lines.extend(days.iter().filter(|d| d.is_registered()).map(|d| {
+        format!(
+            "| {} | {} | {} | {} | {} | {} |",
+            d.year,
+            d.day,
+            d.parts[0].markdown(),
+            d.parts[1].markdown(),
+            if d.has_input { "yes" } else { "no" },
+            d.last_bench_us
+                .map_or_else(|| "-".to_string(), format_micros)
+        )
+    }));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answer::Answer;
+    use crate::test_util::{TempDir, REGISTRY};
+    use std::fs;
+
+    fn entry(day: usize, part: usize, solve_median_us: f64) -> HistoryEntry {
+        HistoryEntry {
+            year: 2022,
+            day,
+            part,
+            commit: "abc1234".to_string(),
+            recorded_at: 0,
+            parse_median_us: 10.0,
+            solve_median_us,
+        }
+    }
+
+    fn progress() -> Vec<DayProgress> {
+        let input_dir = TempDir::new("progress");
+        fs::write(input_file_path(input_dir.path(), 2022, 1), "1\n").unwrap();
+        let known = vec![KnownAnswer {
+            year: 2022,
+            day: 1,
+            part: 1,
+            answer: Answer::from(1usize),
+        }];
+        let history = vec![entry(1, 1, 5000.0), entry(1, 1, 40.0), entry(1, 2, 50.0)];
+
+        collect(REGISTRY, &[2022], &known, &history, input_dir.path())
+    }
+
+    #[test]
+    fn check_collect() {
+        let days = progress();
+        assert_eq!(days.len(), 25);
+        assert_eq!(days[0].parts, [PartState::Verified, PartState::Solved]);
+        assert!(days[0].has_input);
+        assert_eq!(days[0].last_bench_us, Some(100.0));
+        assert_eq!(
+            days[1].parts,
+            [PartState::NoLongerAvailable, PartState::Solved]
+        );
+        assert_eq!(days[2].parts, [PartState::Stubbed, PartState::Stubbed]);
+        assert_eq!(days[4].parts, [PartState::Unregistered; 2]);
+        assert!(!days[4].has_input);
+        assert_eq!(days[4].last_bench_us, None);
+    }
+
+    #[test]
+    fn check_calendar() {
+        let calendar = format_calendar(2022, &progress());
+        let lines = calendar.lines().collect_vec();
+        assert_eq!(lines.len(), 7);
+        assert_eq!(
+            lines[1],
+            " 1 *+ i   100µs |  2 x+ -         |  3 .. -         |  4 .. -         |  5    -"
+        );
+        assert_eq!(
+            lines[6],
+            "1 verified, 2 solved, 4 stubbed, 1 no longer available"
+        );
+    }
+
+    #[test]
+    fn check_markdown() {
+        let markdown = format_markdown(&progress());
+        let lines = markdown.lines().collect_vec();
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[2], "| 2022 | 1 | ⭐ | solved | yes | 100µs |");
+        assert_eq!(
+            lines[3],
+            "| 2022 | 2 | no longer available | solved | no | - |"
+        );
+    }
+
+    #[test]
+    fn check_format_micros() {
+        assert_eq!(format_micros(12.3), "12µs");
+        assert_eq!(format_micros(1234.0), "1.2ms");
+        assert_eq!(format_micros(2_500_000.0), "2.50s");
+    }
+}
@@ -0,0 +1,249 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use anyhow::{Context, Result};
+use itertools::Itertools;
+use regex::Regex;
+
+static ARTICLE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?s)<article class="day-desc">(.*?)</article>"#).unwrap());
+static CODE_BLOCK: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap());
+static ANSWER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)<code><em>(.*?)</em></code>").unwrap());
+static TAG: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<[^>]*>").unwrap());
+static ENTITY: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"&(#[0-9]+|#[xX][0-9a-fA-F]+|[a-z]+);").unwrap());
+
+/// What a saved puzzle description offers for one part.  The page has one description
+/// per part that has been unlocked.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartDescription {
+    /// The text of every `<pre><code>` block, in page order.
+    pub code_blocks: Vec<String>,
+    /// Every highlighted `<code><em>` value, in page order.  The example's answer is
+    /// usually the last.
+    pub answers: Vec<String>,
+}
+
+impl PartDescription {
+    /// The most likely example: the part's first code block and last highlighted value.
+    pub fn example(&self) -> Option<(&str, &str)> {
+        Some((self.code_blocks.first()?, self.answers.last()?))
+    }
+}
+
+/// Splits a saved puzzle page into its part descriptions.
+pub fn parse(html: &str) -> Vec<PartDescription> {
+    ARTICLE
+        .captures_iter(html)
+        .map(|article| {
+            let article = &article[1];
+            PartDescription {
+                code_blocks: CODE_BLOCK
+                    .captures_iter(article)
+                    .map(|block| decode_entities(&strip_tags(&block[1])))
+                    .collect(),
+                answers: ANSWER
+                    .captures_iter(article)
+                    .map(|value| decode_entities(&strip_tags(&value[1])))
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+/// Removes the markup used to highlight parts of a code block.
+fn strip_tags(text: &str) -> String {
+    TAG.replace_all(text, "").into_owned()
+}
+
+/// Decodes the entities the puzzle pages use: `&lt;`, `&gt;`, `&amp;`, `&quot;`,
+/// `&apos;` and numeric references.  Anything else is left as it is.
+pub fn decode_entities(text: &str) -> String {
+    ENTITY
+        .replace_all(text, |captures: &regex::Captures| {
+            let name = &captures[1];
+            let decoded = match name {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                _ => match name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                    None => name.strip_prefix('#').and_then(|dec| dec.parse().ok()),
+                }
+                .and_then(char::from_u32),
+            };
+            decoded.map_or_else(|| captures[0].to_string(), |c| c.to_string())
+        })
+        .into_owned()
+}
+
+/// Writes each part's example and answer as `YYYY-DD-P.txt` and `YYYY-DD-P.answer` in
+/// `dir`.  A part without its own code block reuses the previous part's example, as
+/// part 2 usually does.  The two files are written as a pair, and only if neither has
+/// content yet, so an answer is never paired with an input it didn't come from.  Parts
+/// with no example or no highlighted answer are skipped.  Returns the files written.
+pub fn write_examples(
+    parts: &[PartDescription],
+    dir: &Path,
+    year: usize,
+    day: usize,
+) -> Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let mut written = Vec::new();
+    let mut example_input = None;
+    for (index, part) in parts.iter().enumerate() {
+        let stem = format!("{}-{:02}-{}", year, day, index + 1);
+        if let Some(block) = part.code_blocks.first() {
+            example_input = Some(block.as_str());
+        }
+        let (example_input, answer) = match (example_input, part.answers.last()) {
+            (Some(example_input), Some(answer)) => (example_input, answer),
+            (None, _) => {
+                log::warn!("No example in the description of part {}", index + 1);
+                continue;
+            }
+            (_, None) => {
+                log::warn!(
+                    "No highlighted answer in the description of part {}",
+                    index + 1
+                );
+                continue;
+            }
+        };
+
+        let pair = [
+            (dir.join(format!("{}.txt", stem)), example_input),
+            (dir.join(format!("{}.answer", stem)), answer.as_str()),
+        ];
+        let filled = pair
+            .iter()
+            .filter(|(path, _)| {
+                !fs::read_to_string(path)
+                    .unwrap_or_default()
+                    .trim()
+                    .is_empty()
+            })
+            .count();
+        if filled > 0 {
+            if filled < pair.len() {
+                log::warn!(
+                    "Leaving {} alone, as only one of its input and answer is filled in",
+                    stem
+                );
+            }
+            continue;
+        }
+        for (path, contents) in pair {
+            let contents = format!("{}\n", contents.trim_end_matches('\n'));
+            fs::write(&path, contents)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            written.push(path);
+        }
+    }
+    Ok(written)
+}
+
+/// A one-line summary of a code block, for listing them.
+pub fn preview(block: &str) -> String {
+    let lines = block.lines().collect_vec();
+    let first = lines.first().copied().unwrap_or("");
+    let first = if first.chars().count() > 40 {
+        format!("{}...", first.chars().take(40).collect::<String>())
+    } else {
+        first.to_string()
+    };
+    format!("{} ({} lines)", first, lines.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Test ---</h2><p>For example:</p>
+<pre><code>1 -&gt; 2
+<em>3</em> &lt; 4 &amp;&amp; x
+</code></pre>
+<p>The shortcut is <code>a&lt;b</code>, and the answer is <code><em>7</em></code>.</p>
+<pre><code>unused</code></pre>
+</article>
+<p>Your puzzle answer was <code>1466</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Now try <code><em>twice</em></code>; this gives <code><em>5</em></code>.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn check_parse() {
+        let parts = parse(PAGE);
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].code_blocks, vec!["1 -> 2\n3 < 4 && x\n", "unused"]);
+        assert_eq!(parts[0].answers, vec!["7"]);
+        assert_eq!(parts[0].example(), Some(("1 -> 2\n3 < 4 && x\n", "7")));
+        assert!(parts[1].code_blocks.is_empty());
+        assert_eq!(parts[1].answers, vec!["twice", "5"]);
+    }
+
+    #[test]
+    fn check_decode_entities() {
+        assert_eq!(
+            decode_entities("&lt;&gt;&amp;&quot;&#39;&#x41;&nbsp;&#xZZ;"),
+            "<>&\"'A&nbsp;&#xZZ;"
+        );
+    }
+
+    #[test]
+    fn check_write_examples() {
+        let dir = TempDir::new("page");
+        fs::write(dir.join("2021-01-2.answer"), "").unwrap();
+
+        let written = write_examples(&parse(PAGE), dir.path(), 2021, 1).unwrap();
+        assert_eq!(written.len(), 4);
+        let read = |name: &str| fs::read_to_string(dir.join(name)).unwrap();
+        assert_eq!(read("2021-01-1.txt"), "1 -> 2\n3 < 4 && x\n");
+        assert_eq!(read("2021-01-1.answer"), "7\n");
+        assert_eq!(read("2021-01-2.txt"), "1 -> 2\n3 < 4 && x\n");
+        assert_eq!(read("2021-01-2.answer"), "5\n");
+        assert!(write_examples(&parse(PAGE), dir.path(), 2021, 1)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn check_write_examples_keeps_pairs_together() {
+        let dir = TempDir::new("page-pairs");
+        fs::write(dir.join("2021-01-1.txt"), "").unwrap();
+        fs::write(dir.join("2021-01-1.answer"), "kept\n").unwrap();
+
+        let written = write_examples(&parse(PAGE), dir.path(), 2021, 1).unwrap();
+        assert_eq!(
+            written,
+            vec![dir.join("2021-01-2.txt"), dir.join("2021-01-2.answer")]
+        );
+        assert_eq!(fs::read_to_string(dir.join("2021-01-1.txt")).unwrap(), "");
+        assert_eq!(
+            fs::read_to_string(dir.join("2021-01-1.answer")).unwrap(),
+            "kept\n"
+        );
+    }
+
+    #[test]
+    fn check_write_examples_skips_parts_without_answers() {
+        let dir = TempDir::new("page-unanswered");
+        let page = PAGE.replace(
+            "<code><em>twice</em></code>; this gives <code><em>5</em></code>",
+            "",
+        );
+        let written = write_examples(&parse(&page), dir.path(), 2021, 1).unwrap();
+        assert_eq!(
+            written,
+            vec![dir.join("2021-01-1.txt"), dir.join("2021-01-1.answer")]
+        );
+    }
+}
@@ -0,0 +1,515 @@
+use std::fmt;
+use std::num::NonZeroUsize;
+use std::panic;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use itertools::Itertools;
+use serde_json::json;
+
+use crate::answer::Answer;
+use crate::input::{Input, InputSource};
+use crate::solution::{DayRun, PartRun, Registration, RunEvent};
+use crate::{input_file_path, registry};
+
+/// Solutions get a bigger stack than spawned threads' default, in line with the main
+/// thread's, as some of them recurse deeply.
+const SOLUTION_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Status {
+    Ok,
+    MissingInput,
+    Unsolved,
+    /// The part didn't finish within its deadline.
+    TimedOut,
+    /// The error chain, on one line.
+    Error(String),
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Status::Ok => write!(f, "ok"),
+            Status::MissingInput => write!(f, "missing input"),
+            Status::Unsolved => write!(f, "unsolved"),
+            Status::TimedOut => write!(f, "timed out"),
+            Status::Error(_) => write!(f, "error"),
+        }
+    }
+}
+
+/// The result of one part in a bulk run.  Parts run together share a parse and report
+/// the same `parse_duration`, but those run by `run_registered` each parse for
+/// themselves.
+pub struct PartReport {
+    pub year: usize,
+    pub day: usize,
+    pub puzzle: usize,
+    pub answer: Option<Answer>,
+    pub parse_duration: Duration,
+    pub solve_duration: Duration,
+    pub status: Status,
+}
+
+/// The error for a part that didn't finish within its deadline.
+#[derive(Debug)]
+pub struct TimedOut {
+    pub year: usize,
+    pub day: usize,
+    pub puzzle: usize,
+    pub timeout: Duration,
+}
+
+impl fmt::Display for TimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Gave up on day {}, puzzle {} of {} after {:?}",
+            self.day, self.puzzle, self.year, self.timeout
+        )
+    }
+}
+
+impl std::error::Error for TimedOut {}
+
+/// Parses `input` and runs each of `puzzles` against it on a worker thread.  The parse
+/// and the first part share one `timeout` (or none), and each later part gets its own.
+/// A part that misses its deadline fails with `TimedOut`, and the parts after it start
+/// again on a fresh worker, so one hanging part doesn't lose the others' answers.
+///
+/// Threads can't be killed, so an abandoned worker carries on in the background until
+/// the process exits, competing with whatever runs next.
+pub fn run_with_deadlines(
+    registration: &'static Registration,
+    puzzles: &[usize],
+    input: Arc<Input>,
+    timeout: Option<Duration>,
+) -> Result<DayRun> {
+    let mut day_run = DayRun {
+        parse_duration: Duration::ZERO,
+        parse_allocations: None,
+        parts: Vec::with_capacity(puzzles.len()),
+    };
+    let mut parsed = false;
+    let mut remaining = puzzles;
+    while !remaining.is_empty() {
+        let (sender, receiver) = mpsc::channel();
+        let worker = {
+            let (input, puzzles) = (Arc::clone(&input), remaining.to_vec());
+            thread::Builder::new()
+                .name("solution".to_string())
+                .stack_size(SOLUTION_STACK_SIZE)
+                .spawn(move || {
+                    // The receiver is gone if we've stopped waiting.
+                    let result = registration.run_each(&puzzles, &input, &mut |event| {
+                        let _ = sender.send(Ok(event));
+                    });
+                    if let Err(err) = result {
+                        let _ = sender.send(Err(err));
+                    }
+                })
+                .expect("Failed to start a solution thread")
+        };
+
+        let mut deadline = timeout.map(|timeout| Instant::now() + timeout);
+        loop {
+            let event = match deadline {
+                Some(deadline) => {
+                    receiver.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                }
+                None => receiver.recv().map_err(RecvTimeoutError::from),
+            };
+            match event {
+                Ok(Ok(RunEvent::Parsed {
+                    duration,
+                    allocations,
+                })) => {
+                    // Keep the first parse; any later one competes with an abandoned worker.
+                    if !parsed {
+                        day_run.parse_duration = duration;
+                        day_run.parse_allocations = allocations;
+                        parsed = true;
+                    }
+                }
+                Ok(Ok(RunEvent::Part(part))) => {
+                    day_run.parts.push(part);
+                    remaining = &remaining[1..];
+                    if remaining.is_empty() {
+                        break;
+                    }
+                    deadline = timeout.map(|timeout| Instant::now() + timeout);
+                }
+                Ok(Err(err)) => return Err(err),
+                Err(RecvTimeoutError::Timeout) => {
+                    let timed_out = TimedOut {
+                        year: registration.year,
+                        day: registration.day,
+                        puzzle: remaining[0],
+                        timeout: timeout.unwrap_or_default(),
+                    };
+                    log::warn!(
+                        "{}; it's still running, so later timings may be slower",
+                        timed_out
+                    );
+                    day_run.parts.push(PartRun {
+                        puzzle: remaining[0],
+                        answer: Err(timed_out.into()),
+                        duration: Duration::ZERO,
+                        allocations: None,
+                    });
+                    remaining = &remaining[1..];
+                    break;
+                }
+                // The worker dropped the sender before finishing, so it panicked.
+                Err(RecvTimeoutError::Disconnected) => match worker.join() {
+                    Err(payload) => panic::resume_unwind(payload),
+                    Ok(()) => unreachable!("The worker reports every part or an error"),
+                },
+            }
+        }
+    }
+    Ok(day_run)
+}
+
+/// Runs `puzzles` of a day against the input from `source`.  The input is read first,
+/// and then each part has `timeout` to finish, as in `run_with_deadlines`.
+pub fn run_day(
+    registration: &'static Registration,
+    puzzles: &[usize],
+    source: &InputSource,
+    timeout: Option<Duration>,
+) -> Vec<PartReport> {
+    let report = |puzzle, status, answer, parse_duration, solve_duration| PartReport {
+        year: registration.year,
+        day: registration.day,
+        puzzle,
+        answer,
+        parse_duration,
+        solve_duration,
+        status,
+    };
+    let failed = |status: Status| {
+        puzzles
+            .iter()
+            .map(|&puzzle| report(puzzle, status.clone(), None, Duration::ZERO, Duration::ZERO))
+            .collect()
+    };
+
+    if matches!(source, InputSource::File(path) if !path.exists()) {
+        return failed(Status::MissingInput);
+    }
+    let input = match source.read() {
+        Ok(input) => Arc::new(input),
+        Err(err) => return failed(Status::Error(format!("{:#}", err))),
+    };
+
+    match run_with_deadlines(registration, puzzles, input, timeout) {
+        Ok(DayRun {
+            parse_duration,
+            parts,
+            ..
+        }) => parts
+            .into_iter()
+            .map(|part| {
+                let (status, answer) = match part.answer {
+                    Ok(Some(answer)) => (Status::Ok, Some(answer)),
+                    Ok(None) => (Status::Unsolved, None),
+                    Err(err) if err.is::<TimedOut>() => (Status::TimedOut, None),
+                    Err(err) => (Status::Error(format!("{:#}", err)), None),
+                };
+                report(part.puzzle, status, answer, parse_duration, part.duration)
+            })
+            .collect(),
+        Err(err) => failed(Status::Error(format!("{:#}", err))),
+    }
+}
+
+/// Runs every registered day (of `year`, if given) against its input file in
+/// `input_dir`.  A part that runs past `timeout` is reported as timed out, and the run
+/// moves on without it.
+///
+/// Parts are shared out between `jobs` threads, each part parsing the input for
+/// itself, so that the two parts of a slow day can run at once.  The reports are in
+/// year, day and part order however many jobs there are, but with more than one the
+/// timings include contention for the CPU and memory.
+pub fn run_registered(
+    year: Option<usize>,
+    input_dir: &Path,
+    timeout: Option<Duration>,
+    jobs: NonZeroUsize,
+) -> Vec<PartReport> {
+    let parts = registry()
+        .filter(|r| year.is_none_or(|year| r.year == year))
+        .cartesian_product([1, 2])
+        .collect_vec();
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs.get().min(parts.len()) {
+            let (parts, next, sender) = (&parts, &next, sender.clone());
+            scope.spawn(move || {
+                while let Some(&(r, puzzle)) = parts.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let source = InputSource::File(input_file_path(input_dir, r.year, r.day));
+                    let reports = run_day(r, &[puzzle], &source, timeout);
+                    // The receiver outlives the scope.
+                    sender.send(reports).unwrap();
+                }
+            });
+        }
+    });
+    drop(sender);
+    receiver
+        .into_iter()
+        .flatten()
+        .sorted_by_key(|r| (r.year, r.day, r.puzzle))
+        .collect()
+}
+
+impl PartReport {
+    /// The report as a JSON object, for scripts.  Timings are in microseconds, and are
+    /// zero unless the part ran.
+    pub fn to_json(&self) -> serde_json::Value {
+        let (status, error) = match &self.status {
+            Status::Ok => ("ok", None),
+            Status::MissingInput => ("missing_input", None),
+            Status::Unsolved => ("unsolved", None),
+            Status::TimedOut => ("timed_out", None),
+            Status::Error(err) => ("error", Some(err.as_str())),
+        };
+        json!({
+            "year": self.year,
+            "day": self.day,
+            "part": self.puzzle,
+            "answer": self.answer.as_ref().map(Answer::to_string),
+            "parse_us": self.parse_duration.as_micros() as u64,
+            "solve_us": self.solve_duration.as_micros() as u64,
+            "status": status,
+            "error": error,
+        })
+    }
+}
+
+/// The time spent parsing and solving in a `run_registered` run, where each part
+/// parses the input for itself.
+pub fn total_duration(reports: &[PartReport]) -> Duration {
+    reports
+        .iter()
+        .map(|r| r.parse_duration + r.solve_duration)
+        .sum()
+}
+
+/// Renders `reports` as a plain-text table, one row per part.
+pub fn format_table(reports: &[PartReport]) -> String {
+    let header = [
+        "Year",
+        "Day",
+        "Part",
+        "Answer",
+        "Parse µs",
+        "Solve µs",
+        "Status",
+    ];
+    let rows = reports
+        .iter()
+        .map(|r| {
+            let timed = matches!(r.status, Status::Ok | Status::Unsolved);
+            vec![
+                r.year.to_string(),
+                r.day.to_string(),
+                r.puzzle.to_string(),
+                r.answer.as_ref().map_or(String::new(), Answer::single_line),
+                if timed {
+                    r.parse_duration.as_micros().to_string()
+                } else {
+                    String::new()
+                },
+                if timed {
+                    r.solve_duration.as_micros().to_string()
+                } else {
+                    String::new()
+                },
+                r.status.to_string(),
+            ]
+        })
+        .collect_vec();
+    render_table(&header, &rows)
+}
+
+/// Lays out `rows` under `header` in left-aligned columns.
+pub(crate) fn render_table(header: &[&str], rows: &[Vec<String>]) -> String {
+    let header = header.iter().map(|s| s.to_string()).collect_vec();
+    let widths = (0..header.len())
+        .map(|col| {
+            rows.iter()
+                .chain([&header])
+                .map(|row| row[col].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect_vec();
+    let format_row = |row: &Vec<String>| {
+        row.iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{:<width$}", cell, width = width))
+            .join(" | ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut lines = vec![
+        format_row(&header),
+        widths.iter().map(|&width| "-".repeat(width)).join("-+-"),
+    ];
+    lines.extend(rows.iter().map(format_row));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::examples;
+    use crate::find_solution;
+    use crate::solution::Solution;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn check_missing_input() {
+        let reports = run_day(
+            find_solution(2021, 1).unwrap(),
+            &[1, 2],
+            &InputSource::File("/no/such/input".into()),
+            None,
+        );
+        assert_eq!(reports.len(), 2);
+        assert!(reports.iter().all(|r| r.status == Status::MissingInput));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn check_device_input_is_read() {
+        let reports = run_day(
+            find_solution(2021, 1).unwrap(),
+            &[1],
+            &InputSource::File("/dev/null".into()),
+            None,
+        );
+        assert_ne!(reports[0].status, Status::MissingInput);
+    }
+
+    #[test]
+    fn check_jobs_keep_order() {
+        // The examples stand in for puzzle inputs, and days without one are missing.
+        let dir = TempDir::new("jobs");
+        for example in examples::discover(&examples::examples_dir()).unwrap() {
+            let input_path = input_file_path(dir.path(), example.year, example.day);
+            fs::copy(&example.input_path, input_path).unwrap();
+        }
+        let outcomes = |jobs| {
+            run_registered(None, dir.path(), None, NonZeroUsize::new(jobs).unwrap())
+                .into_iter()
+                .map(|r| (r.year, r.day, r.puzzle, r.answer, r.status))
+                .collect_vec()
+        };
+        let sequential = outcomes(1);
+        assert_eq!(sequential.len(), registry().count() * 2);
+        assert_eq!(
+            sequential[..2],
+            [
+                (2021, 1, 1, Some(Answer::from(7usize)), Status::Ok),
+                (2021, 1, 2, Some(Answer::from(5usize)), Status::Ok),
+            ]
+        );
+        assert!(sequential.iter().any(|o| o.4 == Status::MissingInput));
+        assert_eq!(outcomes(4), sequential);
+    }
+
+    #[test]
+    fn check_deadline_is_per_part() {
+        struct Hangs;
+        impl Solution for Hangs {
+            type Parsed = ();
+            fn parse(_input: &Input) -> anyhow::Result<()> {
+                Ok(())
+            }
+            fn part1(_parsed: &()) -> anyhow::Result<Option<Answer>> {
+                Ok(Some(Answer::from(1usize)))
+            }
+            fn part2(_parsed: &()) -> anyhow::Result<Option<Answer>> {
+                thread::sleep(Duration::from_secs(2));
+                Ok(None)
+            }
+        }
+        static HANGS: Registration = Registration::new::<Hangs>(2021, 99);
+
+        let day_run = run_with_deadlines(
+            &HANGS,
+            &[1, 2, 1],
+            Arc::new(Input::from("")),
+            Some(Duration::from_millis(50)),
+        )
+        .unwrap();
+        let outcomes = day_run
+            .parts
+            .into_iter()
+            .map(|part| match part.answer {
+                Ok(answer) => (part.puzzle, Some(answer)),
+                Err(err) => {
+                    assert!(err.is::<TimedOut>());
+                    (part.puzzle, None)
+                }
+            })
+            .collect_vec();
+        assert_eq!(
+            outcomes,
+            vec![
+                (1, Some(Some(Answer::from(1usize)))),
+                (2, None),
+                (1, Some(Some(Answer::from(1usize))))
+            ]
+        );
+    }
+
+    #[test]
+    fn check_table_layout() {
+        let reports = vec![PartReport {
+            year: 2021,
+            day: 1,
+            puzzle: 1,
+            answer: Some(Answer::from(7usize)),
+            parse_duration: Duration::from_micros(12),
+            solve_duration: Duration::from_micros(3),
+            status: Status::Ok,
+        }];
+        assert_eq!(
+            format_table(&reports),
+            "Year | Day | Part | Answer | Parse µs | Solve µs | Status\n\
+             -----+-----+------+--------+----------+----------+-------\n\
+             2021 | 1   | 1    | 7      | 12       | 3        | ok"
+        );
+    }
+
+    #[test]
+    fn check_json_report() {
+        let report = PartReport {
+            year: 2021,
+            day: 1,
+            puzzle: 2,
+            answer: None,
+            parse_duration: Duration::from_micros(12),
+            solve_duration: Duration::ZERO,
+            status: Status::Error("bad line".to_string()),
+        };
+        assert_eq!(
+            report.to_json().to_string(),
+            r#"{"answer":null,"day":1,"error":"bad line","parse_us":12,"part":2,"solve_us":0,"status":"error","year":2021}"#
+        );
+    }
+}
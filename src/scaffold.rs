@@ -0,0 +1,352 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{anyhow, Context, Result};
+use itertools::Itertools;
+use regex::Regex;
+
+/// The files `new_day` wrote, relative to the crate root.  Files that already existed
+/// and needed no change are not listed, so running it twice reports nothing the second
+/// time.
+#[derive(Debug, Default, PartialEq)]
+pub struct Scaffolded {
+    pub created: Vec<PathBuf>,
+    pub updated: Vec<PathBuf>,
+}
+
+const SOLUTION_TEMPLATE: &str = "\
+use anyhow::Result;
+
+use crate::input::Input;
+use crate::solution::{Progress, Solution};
+
+pub struct DayDD;
+
+impl Solution for DayDD {
+    type Parsed = Vec<String>;
+    // Mark each part Progress::Solved as it's solved, and drop this once both are.
+    const PROGRESS: [Progress; 2] = [Progress::Stubbed, Progress::Stubbed];
+
+    fn parse(input: &Input) -> Result<Vec<String>> {
+        Ok(input.lines().map(|s| s.to_string()).collect())
+    }
+}
+";
+
+const YEAR_TEMPLATE: &str = "\
+use crate::solution::Registration;
+
+pub const SOLUTIONS: &[Registration] = &[
+    // Add next day above this line.
+];
+";
+
+/// Adds a solution module for the day to the crate at `root`, registers it (adding the
+/// year module too if needed), and creates empty example and answer files for both
+/// parts.  Anything that already exists is left alone.
+pub fn new_day(root: &Path, year: usize, day: usize) -> Result<Scaffolded> {
+    let mut scaffolded = Scaffolded::default();
+    let year_dir = PathBuf::from("src").join(format!("y{}", year));
+    let mod_path = year_dir.join("mod.rs");
+
+    // The year is registered even if its module exists, in case an earlier run
+    // stopped partway.
+    fs::create_dir_all(root.join(&year_dir))
+        .with_context(|| format!("Failed to create {}", year_dir.display()))?;
+    scaffolded.create(root, &mod_path, YEAR_TEMPLATE)?;
+    scaffolded.edit(root, Path::new("src/lib.rs"), |text| {
+        let line = format!("mod y{};", year);
+        insert_ordered(text, r"^mod y(\d+);$", &line, year)
+    })?;
+    scaffolded.edit(root, Path::new("src/solution.rs"), |text| {
+        add_year_to_registry(text, year)
+    })?;
+
+    let module = format!("day{:02}", day);
+    let solution = SOLUTION_TEMPLATE.replace("DD", &format!("{:02}", day));
+    scaffolded.create(root, &year_dir.join(format!("{}.rs", module)), &solution)?;
+    scaffolded.edit(root, &mod_path, |text| {
+        let declaration = format!("pub mod {};", module);
+        let text = match insert_ordered(text, r"^pub mod day(\d+);$", &declaration, day) {
+            Ok(text) => text,
+            Err(_) => insert_mod_after_use(text, &declaration),
+        };
+        let registration = format!(
+            "    Registration::new::<{}::Day{:02}>({}, {}),",
+            module, day, year, day
+        );
+        match insert_ordered(
+            &text,
+            r"^    Registration::new::<day(\d+)::",
+            &registration,
+            day,
+        ) {
+            Ok(text) => Ok(text),
+            Err(_) => insert_before_marker(&text, &registration),
+        }
+    })?;
+
+    let examples_dir = Path::new("inputs").join("examples");
+    fs::create_dir_all(root.join(&examples_dir))
+        .with_context(|| format!("Failed to create {}", examples_dir.display()))?;
+    for part in 1..=2 {
+        let stem = format!("{}-{:02}-{}", year, day, part);
+        scaffolded.create(root, &examples_dir.join(format!("{}.txt", stem)), "")?;
+        scaffolded.create(root, &examples_dir.join(format!("{}.answer", stem)), "")?;
+    }
+    Ok(scaffolded)
+}
+
+impl Scaffolded {
+    fn create(&mut self, root: &Path, path: &Path, contents: &str) -> Result<()> {
+        let full_path = root.join(path);
+        if !full_path.exists() {
+            fs::write(&full_path, contents)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            self.created.push(path.to_path_buf());
+        }
+        Ok(())
+    }
+
+    fn edit(
+        &mut self,
+        root: &Path,
+        path: &Path,
+        change: impl FnOnce(&str) -> Result<String>,
+    ) -> Result<()> {
+        let full_path = root.join(path);
+        let text = fs::read_to_string(&full_path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let changed =
+            change(&text).with_context(|| format!("Failed to update {}", path.display()))?;
+        if changed != text {
+            fs::write(&full_path, changed)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            self.updated.push(path.to_path_buf());
+        }
+        Ok(())
+    }
+}
+
+/// Inserts `line` among the lines matching `pattern`, keeping the numbers they capture
+/// in order, unless it is already there.  Fails if no line matches.
+fn insert_ordered(text: &str, pattern: &str, line: &str, key: usize) -> Result<String> {
+    let pattern = Regex::new(pattern).expect("Scaffolding patterns are valid");
+    let mut lines = text.lines().collect_vec();
+    if lines.contains(&line) {
+        return Ok(text.to_string());
+    }
+    let matches = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, l)| {
+            let number = pattern.captures(l)?[1].parse::<usize>().ok()?;
+            Some((index, number))
+        })
+        .collect_vec();
+    let (last, _) = matches
+        .last()
+        .ok_or_else(|| anyhow!("No line matches {}", pattern))?;
+    let index = matches
+        .iter()
+        .find(|(_, number)| *number > key)
+        .map_or(last + 1, |(index, _)| *index);
+    lines.insert(index, line);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Declares the first day module of a new year, between the imports and `SOLUTIONS`.
+fn insert_mod_after_use(text: &str, declaration: &str) -> String {
+    text.replacen(
+        "\n\npub const SOLUTIONS",
+        &format!("\n\n{}\n\npub const SOLUTIONS", declaration),
+        1,
+    )
+}
+
+/// Registers the first day of a new year, just above the marker comment.
+fn insert_before_marker(text: &str, registration: &str) -> Result<String> {
+    const MARKER: &str = "    // Add next day above this line.";
+    if !text.contains(MARKER) {
+        return Err(anyhow!("Missing the {:?} marker", MARKER.trim()));
+    }
+    Ok(text.replacen(MARKER, &format!("{}\n{}", registration, MARKER), 1))
+}
+
+/// Adds the year's module to the `use` and the `YEARS` list in solution.rs.
+fn add_year_to_registry(text: &str, year: usize) -> Result<String> {
+    let use_pattern = Regex::new(r"use crate::\{(y\d+(?:, y\d+)*)\};").unwrap();
+    let years_pattern =
+        Regex::new(r"const YEARS: &\[&\[Registration\]\] = &\[([^\]]*)\];").unwrap();
+
+    let modules = use_pattern
+        .captures(text)
+        .ok_or_else(|| anyhow!("Missing the `use` of the year modules"))?[1]
+        .split(", ")
+        .map(|module| module.to_string())
+        .chain([format!("y{}", year)])
+        .sorted()
+        .dedup()
+        .collect_vec();
+    let text = use_pattern.replace(text, format!("use crate::{{{}}};", modules.join(", ")));
+
+    if !years_pattern.is_match(&text) {
+        return Err(anyhow!("Missing the YEARS list"));
+    }
+    let one_line = format!(
+        "const YEARS: &[&[Registration]] = &[{}];",
+        modules
+            .iter()
+            .map(|m| format!("{}::SOLUTIONS", m))
+            .join(", ")
+    );
+    // Wrap the way rustfmt would once it no longer fits on a line.
+    let years = if one_line.len() <= 100 {
+        one_line
+    } else {
+        format!(
+            "const YEARS: &[&[Registration]] = &[\n{}];",
+            modules
+                .iter()
+                .map(|m| format!("    {}::SOLUTIONS,\n", m))
+                .join("")
+        )
+    };
+    Ok(years_pattern.replace(&text, years.as_str()).into_owned())
+}
+
+/// Switches to the `YEAR-DAY` branch, creating it from the current commit if it doesn't
+/// exist yet.  Returns the branch name.
+pub fn checkout_branch(root: &Path, year: usize, day: usize) -> Result<String> {
+    let branch = format!("{}-{}", year, day);
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(root)
+            .status()
+            .with_context(|| format!("Failed to run git {}", args.join(" ")))
+    };
+    let exists = git(&[
+        "rev-parse",
+        "--verify",
+        "--quiet",
+        &format!("refs/heads/{}", branch),
+    ])?
+    .success();
+    let status = if exists {
+        git(&["checkout", &branch])?
+    } else {
+        git(&["checkout", "-b", &branch])?
+    };
+    if !status.success() {
+        return Err(anyhow!("Failed to check out branch {}", branch));
+    }
+    Ok(branch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn fake_crate(name: &str) -> TempDir {
+        let root = TempDir::new(&format!("scaffold-{}", name));
+        fs::create_dir_all(root.join("src/y2023")).unwrap();
+        fs::write(
+            root.join("src/lib.rs"),
+            "mod solution;\nmod y2021;\nmod y2023;\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("src/solution.rs"),
+            "use crate::{y2021, y2023};\n\n\
+             const YEARS: &[&[Registration]] = &[y2021::SOLUTIONS, y2023::SOLUTIONS];\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("src/y2023/mod.rs"),
+            "use crate::solution::Registration;\n\n\
+             pub mod day01;\npub mod day03;\n\n\
+             pub const SOLUTIONS: &[Registration] = &[\n\
+             \x20   Registration::new::<day01::Day01>(2023, 1),\n\
+             \x20   Registration::new::<day03::Day03>(2023, 3),\n\
+             \x20   // Add next day above this line.\n\
+             ];\n",
+        )
+        .unwrap();
+        root
+    }
+
+    #[test]
+    fn check_new_day_in_existing_year() {
+        let root = fake_crate("existing");
+        let scaffolded = new_day(root.path(), 2023, 2).unwrap();
+        assert_eq!(scaffolded.updated, vec![PathBuf::from("src/y2023/mod.rs")]);
+        assert_eq!(scaffolded.created.len(), 5);
+
+        let module = fs::read_to_string(root.join("src/y2023/mod.rs")).unwrap();
+        assert!(module.contains("pub mod day01;\npub mod day02;\npub mod day03;\n"));
+        assert!(module.contains(
+            "(2023, 1),\n    Registration::new::<day02::Day02>(2023, 2),\n    Registration::new::<day03"
+        ));
+        let solution = fs::read_to_string(root.join("src/y2023/day02.rs")).unwrap();
+        assert!(solution.contains("impl Solution for Day02 {"));
+        assert!(solution
+            .contains("const PROGRESS: [Progress; 2] = [Progress::Stubbed, Progress::Stubbed];"));
+        assert!(root.join("inputs/examples/2023-02-2.answer").is_file());
+
+        // A second run changes nothing.
+        assert_eq!(
+            new_day(root.path(), 2023, 2).unwrap(),
+            Scaffolded::default()
+        );
+    }
+
+    #[test]
+    fn check_new_day_in_new_year() {
+        let root = fake_crate("new-year");
+        new_day(root.path(), 2022, 5).unwrap();
+
+        let lib = fs::read_to_string(root.join("src/lib.rs")).unwrap();
+        assert_eq!(lib, "mod solution;\nmod y2021;\nmod y2022;\nmod y2023;\n");
+        let registry = fs::read_to_string(root.join("src/solution.rs")).unwrap();
+        assert!(registry.contains("use crate::{y2021, y2022, y2023};"));
+        assert!(registry.contains("&[y2021::SOLUTIONS, y2022::SOLUTIONS, y2023::SOLUTIONS];"));
+        let module = fs::read_to_string(root.join("src/y2022/mod.rs")).unwrap();
+        assert_eq!(
+            module,
+            "use crate::solution::Registration;\n\npub mod day05;\n\n\
+             pub const SOLUTIONS: &[Registration] = &[\n\
+             \x20   Registration::new::<day05::Day05>(2022, 5),\n\
+             \x20   // Add next day above this line.\n\
+             ];\n"
+        );
+
+        assert_eq!(
+            new_day(root.path(), 2022, 5).unwrap(),
+            Scaffolded::default()
+        );
+    }
+
+    #[test]
+    fn check_new_year_recovers_from_partial_run() {
+        let root = fake_crate("partial");
+        fs::create_dir_all(root.join("src/y2022")).unwrap();
+        fs::write(root.join("src/y2022/mod.rs"), YEAR_TEMPLATE).unwrap();
+        let scaffolded = new_day(root.path(), 2022, 5).unwrap();
+        assert_eq!(
+            scaffolded.updated,
+            vec![
+                PathBuf::from("src/lib.rs"),
+                PathBuf::from("src/solution.rs"),
+                PathBuf::from("src/y2022/mod.rs"),
+            ]
+        );
+
+        let lib = fs::read_to_string(root.join("src/lib.rs")).unwrap();
+        assert_eq!(lib, "mod solution;\nmod y2021;\nmod y2022;\nmod y2023;\n");
+        let registry = fs::read_to_string(root.join("src/solution.rs")).unwrap();
+        assert!(registry.contains("&[y2021::SOLUTIONS, y2022::SOLUTIONS, y2023::SOLUTIONS];"));
+    }
+}
@@ -0,0 +1,337 @@
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Context, Result};
+use itertools::Itertools;
+
+use crate::alloc_stats::{self, AllocStats};
+use crate::answer::Answer;
+use crate::input::Input;
+use crate::{y2021, y2022, y2023};
+
+/// A single day's puzzle.  The input is parsed once into `Parsed`, and each part is
+/// computed from that.  `parse` picks whichever view of the `Input` suits the puzzle
+/// format.  Parts that have not been solved yet can rely on the default
+/// implementations, which return `Ok(None)`.
+pub trait Solution {
+    type Parsed;
+
+    /// How far each part has got, as shown by `aoc list`.
+    const PROGRESS: [Progress; 2] = [Progress::Solved, Progress::Solved];
+
+    fn parse(input: &Input) -> Result<Self::Parsed>;
+
+    fn part1(_parsed: &Self::Parsed) -> Result<Option<Answer>> {
+        Ok(None)
+    }
+
+    fn part2(_parsed: &Self::Parsed) -> Result<Option<Answer>> {
+        Ok(None)
+    }
+}
+
+/// Whether a part has a working solution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Progress {
+    Solved,
+    /// Not written yet; the part returns `Ok(None)`.
+    Stubbed,
+    /// Solved once, but the code was later reworked for the other part and the
+    /// original answer can no longer be computed.
+    NoLongerAvailable,
+}
+
+/// Context attached to errors from `Solution::parse`, so that callers can tell a bad
+/// puzzle input apart from a failing solution.
+#[derive(Debug)]
+pub struct BadInput {
+    pub year: usize,
+    pub day: usize,
+}
+
+impl fmt::Display for BadInput {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Bad input for day {} of {}", self.day, self.year)
+    }
+}
+
+/// The outcome of running one part against an already-parsed input.
+pub struct PartRun {
+    pub puzzle: usize,
+    pub answer: Result<Option<Answer>>,
+    pub duration: Duration,
+    /// Only counted with the `count-allocations` feature.
+    pub allocations: Option<AllocStats>,
+}
+
+/// The outcome of parsing a day's input once and running parts against it.
+pub struct DayRun {
+    pub parse_duration: Duration,
+    /// Only counted with the `count-allocations` feature.
+    pub parse_allocations: Option<AllocStats>,
+    pub parts: Vec<PartRun>,
+}
+
+/// What a run reports as it goes: the parse, then each part as it finishes.
+pub enum RunEvent {
+    Parsed {
+        duration: Duration,
+        allocations: Option<AllocStats>,
+    },
+    Part(PartRun),
+}
+
+type Runner = fn(&Registration, &[usize], &Input, &mut dyn FnMut(RunEvent)) -> Result<()>;
+
+/// A `Solution` registered against the year and day it solves.
+pub struct Registration {
+    pub year: usize,
+    pub day: usize,
+    pub progress: [Progress; 2],
+    run: Runner,
+}
+
+impl Registration {
+    pub const fn new<S: Solution>(year: usize, day: usize) -> Registration {
+        Registration {
+            year,
+            day,
+            progress: S::PROGRESS,
+            run: run_puzzles::<S>,
+        }
+    }
+
+    /// Parses the input once and runs each of `puzzles` against it.  Only a parse
+    /// failure is returned as an error; failing parts are reported in their `PartRun`.
+    pub fn run_parts(&self, puzzles: &[usize], input: &Input) -> Result<DayRun> {
+        let mut day_run = DayRun {
+            parse_duration: Duration::ZERO,
+            parse_allocations: None,
+            parts: Vec::with_capacity(puzzles.len()),
+        };
+        self.run_each(puzzles, input, &mut |event| match event {
+            RunEvent::Parsed {
+                duration,
+                allocations,
+            } => {
+                day_run.parse_duration = duration;
+                day_run.parse_allocations = allocations;
+            }
+            RunEvent::Part(part) => day_run.parts.push(part),
+        })?;
+        Ok(day_run)
+    }
+
+    /// Like `run_parts`, but hands each step to `on_event` as soon as it's done, so
+    /// that a caller can keep the parts that finished if a later one never does.
+    pub fn run_each(
+        &self,
+        puzzles: &[usize],
+        input: &Input,
+        on_event: &mut dyn FnMut(RunEvent),
+    ) -> Result<()> {
+        (self.run)(self, puzzles, input, on_event)
+    }
+
+    pub fn run(&self, puzzle: usize, input: &Input) -> Result<Option<Answer>> {
+        let mut day_run = self.run_parts(&[puzzle], input)?;
+        day_run.parts.remove(0).answer
+    }
+
+    pub fn bad_input(&self) -> BadInput {
+        BadInput {
+            year: self.year,
+            day: self.day,
+        }
+    }
+}
+
+fn run_puzzles<S: Solution>(
+    registration: &Registration,
+    puzzles: &[usize],
+    input: &Input,
+    on_event: &mut dyn FnMut(RunEvent),
+) -> Result<()> {
+    let now = Instant::now();
+    let (parsed, parse_allocations) = alloc_stats::measure(|| catch_panic(|| S::parse(input)));
+    let parse_duration = now.elapsed();
+    let parsed = parsed.context(registration.bad_input())?;
+    on_event(RunEvent::Parsed {
+        duration: parse_duration,
+        allocations: parse_allocations,
+    });
+
+    for &puzzle in puzzles {
+        let now = Instant::now();
+        let (answer, allocations) = alloc_stats::measure(|| {
+            catch_panic(|| match puzzle {
+                1 => S::part1(&parsed),
+                2 => S::part2(&parsed),
+                _ => Ok(None),
+            })
+        });
+        let duration = now.elapsed();
+        on_event(RunEvent::Part(PartRun {
+            puzzle,
+            answer: answer.with_context(|| {
+                format!(
+                    "Solution failed for day {}, puzzle {} of {}",
+                    registration.day, puzzle, registration.year
+                )
+            }),
+            duration,
+            allocations,
+        }));
+    }
+    Ok(())
+}
+
+/// Runs `f`, turning a panic into an error so that one broken part doesn't take the
+/// rest of the run down with it.
+fn catch_panic<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let message = match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => match payload.downcast::<&str>() {
+                Ok(message) => message.to_string(),
+                Err(_) => "unknown cause".to_string(),
+            },
+        };
+        Err(anyhow!("Panicked: {}", message))
+    })
+}
+
+const YEARS: &[&[Registration]] = &[y2021::SOLUTIONS, y2022::SOLUTIONS, y2023::SOLUTIONS];
+
+/// Every registered solution, in year and day order.
+pub fn registry() -> impl Iterator<Item = &'static Registration> {
+    YEARS.iter().flat_map(|solutions| solutions.iter())
+}
+
+pub fn find_solution(year: usize, day: usize) -> Option<&'static Registration> {
+    registry().find(|r| r.year == year && r.day == day)
+}
+
+/// Explains that there's no solution for the day among `registrations`, and what
+/// there is instead.
+pub fn describe_missing_solution<'a>(
+    registrations: impl IntoIterator<Item = &'a Registration>,
+    year: usize,
+    day: usize,
+) -> String {
+    let registered = registrations
+        .into_iter()
+        .map(|r| (r.year, r.day))
+        .collect_vec();
+    let days = registered
+        .iter()
+        .filter(|&&(registered_year, _)| registered_year == year)
+        .map(|&(_, day)| day)
+        .collect_vec();
+    if days.is_empty() {
+        let years = registered.iter().map(|&(year, _)| year).dedup().join(", ");
+        format!(
+            "No solutions registered for {}; there are solutions for {}",
+            year, years
+        )
+    } else {
+        let instead = match days.as_slice() {
+            [only] => format!("there is a solution for day {}", only),
+            _ => format!("there are solutions for days {}", days.iter().join(", ")),
+        };
+        format!(
+            "No solution registered for day {} of {}; {}",
+            day, year, instead
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::REGISTRY;
+
+    #[test]
+    fn check_registry_is_unique_and_ordered() {
+        let keys = registry().map(|r| (r.year, r.day)).collect_vec();
+        let mut sorted = keys.clone();
+        sorted.sort_unstable();
+        sorted.dedup();
+        assert_eq!(keys, sorted);
+    }
+
+    #[test]
+    fn check_find_solution() {
+        assert!(find_solution(2021, 1).is_some());
+        assert!(find_solution(2021, 26).is_none());
+    }
+
+    #[test]
+    fn check_describe_missing_solution() {
+        assert_eq!(
+            describe_missing_solution(REGISTRY, 2021, 9),
+            "No solution registered for day 9 of 2021; there are solutions for days 1, 3"
+        );
+        assert_eq!(
+            describe_missing_solution(REGISTRY, 2023, 9),
+            "No solution registered for day 9 of 2023; there is a solution for day 1"
+        );
+        assert_eq!(
+            describe_missing_solution(REGISTRY, 2019, 1),
+            "No solutions registered for 2019; there are solutions for 2021, 2022, 2023"
+        );
+    }
+
+    #[test]
+    fn check_bad_input_is_distinguishable() {
+        let input = Input::from("forward 5\nsideways 2\n");
+        let err = find_solution(2021, 2).unwrap().run(2, &input).unwrap_err();
+        assert!(err.is::<BadInput>());
+        assert!(format!("{:?}", err).contains("line 2"));
+    }
+
+    #[test]
+    fn check_panics_are_errors() {
+        struct Panics;
+        impl Solution for Panics {
+            type Parsed = ();
+            fn parse(_input: &Input) -> Result<()> {
+                Ok(())
+            }
+            fn part1(_parsed: &()) -> Result<Option<Answer>> {
+                panic!("index out of bounds")
+            }
+            fn part2(_parsed: &()) -> Result<Option<Answer>> {
+                Ok(Some(Answer::from(2usize)))
+            }
+        }
+
+        let registration = Registration::new::<Panics>(2021, 99);
+        let mut parts = registration
+            .run_parts(&[1, 2], &Input::from(""))
+            .unwrap()
+            .parts;
+        let err = parts.remove(0).answer.unwrap_err();
+        assert!(format!("{:#}", err).ends_with("Panicked: index out of bounds"));
+        assert_eq!(parts.remove(0).answer.unwrap(), Some(Answer::from(2usize)));
+    }
+
+    #[test]
+    fn check_run_parts_shares_one_parse() {
+        let input = Input::from("1\n3\n2\n4\n5\n");
+        let day_run = find_solution(2021, 1)
+            .unwrap()
+            .run_parts(&[1, 2], &input)
+            .unwrap();
+        let answers = day_run
+            .parts
+            .into_iter()
+            .map(|part| (part.puzzle, part.answer.unwrap().unwrap()))
+            .collect_vec();
+        assert_eq!(
+            answers,
+            vec![(1, Answer::from(3usize)), (2, Answer::from(2usize))]
+        );
+    }
+}
@@ -0,0 +1,308 @@
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::client::Client;
+
+/// What the site said about a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "verdict", rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, with no hint which way.
+    Wrong,
+    /// Submitted too soon after a wrong answer; the answer wasn't checked.
+    Wait {
+        seconds: u64,
+    },
+    /// The part has already been solved, so the answer wasn't checked.
+    AlreadySolved,
+    /// A response this tool doesn't recognise, with its text.
+    Unknown {
+        message: String,
+    },
+}
+
+impl Verdict {
+    /// Whether the site judged the answer to be wrong.
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "wrong, too high"),
+            Verdict::TooLow => write!(f, "wrong, too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::Wait { seconds } => write!(f, "not checked, wait {} seconds", seconds),
+            Verdict::AlreadySolved => write!(f, "not checked, already solved"),
+            Verdict::Unknown { message } => write!(f, "unrecognised response: {}", message),
+        }
+    }
+}
+
+/// Reads the verdict from the page the site returns for a submission.
+pub fn parse_verdict(html: &str) -> Verdict {
+    let article = Regex::new(r"(?s)<article>(.*?)</article>").unwrap();
+    let message = article
+        .captures(html)
+        .map_or(html, |captures| captures.get(1).unwrap().as_str());
+    let message = Regex::new(r"<[^>]*>").unwrap().replace_all(message, "");
+    let message = message.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if message.contains("That's the right answer") {
+        Verdict::Correct
+    } else if message.contains("That's not the right answer") {
+        if message.contains("too high") {
+            Verdict::TooHigh
+        } else if message.contains("too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        }
+    } else if message.contains("You gave an answer too recently") {
+        let wait = Regex::new(r"(?:(\d+)m )?(\d+)s left to wait").unwrap();
+        let seconds = wait.captures(&message).map_or(60, |captures| {
+            let minutes = captures.get(1).map_or(0, |m| m.as_str().parse().unwrap());
+            let seconds: u64 = captures[2].parse().unwrap();
+            minutes * 60 + seconds
+        });
+        Verdict::Wait { seconds }
+    } else if message.contains("You don't seem to be solving the right level") {
+        Verdict::AlreadySolved
+    } else {
+        Verdict::Unknown { message }
+    }
+}
+
+/// A submitted answer and what the site said about it.  The guess history holds one
+/// per line, in the order they were submitted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Guess {
+    pub year: usize,
+    pub day: usize,
+    pub part: usize,
+    pub answer: String,
+    /// Seconds since the Unix epoch.
+    pub submitted_at: u64,
+    #[serde(flatten)]
+    pub verdict: Verdict,
+}
+
+/// The guess history, kept next to the inputs in `input_dir` so it isn't lost between
+/// runs.
+pub fn guesses_file_path(input_dir: &Path) -> PathBuf {
+    input_dir.join("guesses.jsonl")
+}
+
+/// Reads every guess from the history file.  A missing file is an empty history.
+pub fn load_guesses(path: &Path) -> Result<Vec<Guess>> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err).with_context(|| format!("Failed to read {}", path.display())),
+    };
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line).with_context(|| {
+                format!("Failed to parse line {} of {}", index + 1, path.display())
+            })
+        })
+        .collect()
+}
+
+pub fn record_guess(path: &Path, guess: &Guess) -> Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    let line = serde_json::to_string(guess).expect("Guesses always serialize");
+    writeln!(file, "{}", line).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Checks an answer against earlier guesses for the same part, failing if it is already
+/// known to be wrong, if the part has already been solved, or if it is outside the
+/// bounds set by earlier "too high" and "too low" verdicts.
+pub fn check_guess(
+    guesses: &[Guess],
+    year: usize,
+    day: usize,
+    part: usize,
+    answer: &str,
+) -> Result<()> {
+    let earlier = guesses
+        .iter()
+        .filter(|g| g.year == year && g.day == day && g.part == part);
+    let value = answer.parse::<i128>().ok();
+    for guess in earlier {
+        if guess.verdict == Verdict::Correct {
+            return Err(anyhow!("Already solved: {} was accepted", guess.answer));
+        }
+        if guess.answer == answer && guess.verdict.is_wrong() {
+            return Err(anyhow!(
+                "{} was already rejected ({})",
+                answer,
+                guess.verdict
+            ));
+        }
+        let bound = guess.answer.parse::<i128>().ok();
+        match (value, bound, &guess.verdict) {
+            (Some(value), Some(bound), Verdict::TooHigh) if value >= bound => {
+                return Err(anyhow!("{} is too high: {} already was", answer, bound))
+            }
+            (Some(value), Some(bound), Verdict::TooLow) if value <= bound => {
+                return Err(anyhow!("{} is too low: {} already was", answer, bound))
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Submits an answer unless earlier guesses rule it out, and records the verdict.
+pub fn submit(
+    client: &Client,
+    history_path: &Path,
+    year: usize,
+    day: usize,
+    part: usize,
+    answer: &str,
+    submitted_at: u64,
+) -> Result<Verdict> {
+    let guesses = load_guesses(history_path)?;
+    check_guess(&guesses, year, day, part, answer)?;
+
+    let verdict = parse_verdict(&client.submit(year, day, part, answer)?);
+    record_guess(
+        history_path,
+        &Guess {
+            year,
+            day,
+            part,
+            answer: answer.to_string(),
+            submitted_at,
+            verdict: verdict.clone(),
+        },
+    )?;
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::test_server::serve_once;
+    use crate::test_util::TempDir;
+
+    fn guess(answer: &str, verdict: Verdict) -> Guess {
+        Guess {
+            year: 2021,
+            day: 1,
+            part: 1,
+            answer: answer.to_string(),
+            submitted_at: 0,
+            verdict,
+        }
+    }
+
+    #[test]
+    fn check_parse_verdict() {
+        let page =
+            |message: &str| format!("<main>\n<article><p>{}</p></article>\n</main>", message);
+        assert_eq!(
+            parse_verdict(&page(
+                "That's the right answer!  You are <em>one gold star</em> closer."
+            )),
+            Verdict::Correct
+        );
+        assert_eq!(
+            parse_verdict(&page(
+                "That's not the right answer; your answer is too high.  If you're stuck..."
+            )),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            parse_verdict(&page(
+                "That's not the right answer; your answer is too low."
+            )),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            parse_verdict(&page("That's not the right answer.  If you're stuck...")),
+            Verdict::Wrong
+        );
+        assert_eq!(
+            parse_verdict(&page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait.")),
+            Verdict::Wait { seconds: 65 }
+        );
+        assert_eq!(
+            parse_verdict(&page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            )),
+            Verdict::AlreadySolved
+        );
+    }
+
+    #[test]
+    fn check_guess_bounds() {
+        let guesses = vec![
+            guess("100", Verdict::TooHigh),
+            guess("10", Verdict::TooLow),
+            guess("50", Verdict::Wrong),
+            guess("60", Verdict::Wait { seconds: 30 }),
+        ];
+        assert!(check_guess(&guesses, 2021, 1, 1, "50").is_err());
+        assert!(check_guess(&guesses, 2021, 1, 1, "100").is_err());
+        assert!(check_guess(&guesses, 2021, 1, 1, "150").is_err());
+        assert!(check_guess(&guesses, 2021, 1, 1, "10").is_err());
+        assert!(check_guess(&guesses, 2021, 1, 1, "60").is_ok());
+        assert!(check_guess(&guesses, 2021, 1, 1, "99").is_ok());
+        assert!(check_guess(&guesses, 2021, 1, 2, "150").is_ok());
+
+        let solved = vec![guess("42", Verdict::Correct)];
+        assert!(check_guess(&solved, 2021, 1, 1, "43").is_err());
+    }
+
+    #[test]
+    fn check_submit() {
+        let (base_url, server) = serve_once(
+            200,
+            "<article><p>That's not the right answer; your answer is too low.</p></article>",
+        );
+        let client = Client::new(&base_url, "secret");
+        let dir = TempDir::new("guesses");
+        let path = dir.join("guesses.jsonl");
+
+        let verdict = submit(&client, &path, 2021, 1, 2, "1491", 7).unwrap();
+        let request = server.join().unwrap();
+        assert_eq!(verdict, Verdict::TooLow);
+        assert!(request.starts_with("POST /2021/day/1/answer HTTP/1.1\r\n"));
+        assert!(request.ends_with("level=2&answer=1491"));
+        assert_eq!(
+            load_guesses(&path).unwrap(),
+            vec![Guess {
+                year: 2021,
+                day: 1,
+                part: 2,
+                answer: "1491".to_string(),
+                submitted_at: 7,
+                verdict: Verdict::TooLow,
+            }]
+        );
+
+        // Refused locally, without contacting the server (which has gone).
+        assert!(submit(&client, &path, 2021, 1, 2, "1491", 8).is_err());
+    }
+}
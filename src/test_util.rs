@@ -0,0 +1,89 @@
+//! Fixtures shared by the unit tests.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::{env, fs, process};
+
+use anyhow::Result;
+
+use crate::input::Input;
+use crate::solution::{Progress, Registration, Solution};
+
+struct Solved;
+
+impl Solution for Solved {
+    type Parsed = ();
+
+    fn parse(_input: &Input) -> Result<()> {
+        Ok(())
+    }
+}
+
+struct Stubbed;
+
+impl Solution for Stubbed {
+    type Parsed = ();
+    const PROGRESS: [Progress; 2] = [Progress::Stubbed, Progress::Stubbed];
+
+    fn parse(_input: &Input) -> Result<()> {
+        Ok(())
+    }
+}
+
+struct Reworked;
+
+impl Solution for Reworked {
+    type Parsed = ();
+    const PROGRESS: [Progress; 2] = [Progress::NoLongerAvailable, Progress::Solved];
+
+    fn parse(_input: &Input) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// A small registry that doesn't change as real solutions are added.
+pub const REGISTRY: &[Registration] = &[
+    Registration::new::<Solved>(2021, 1),
+    Registration::new::<Solved>(2021, 3),
+    Registration::new::<Solved>(2022, 1),
+    Registration::new::<Reworked>(2022, 2),
+    Registration::new::<Stubbed>(2022, 3),
+    Registration::new::<Stubbed>(2022, 4),
+    Registration::new::<Stubbed>(2023, 1),
+];
+
+/// The sonar sweep example from day 1 of 2021, whose answers are 7 and 5.
+pub const DEPTHS: &str = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
+
+/// A fresh, empty directory for one test, removed when dropped so that a failing
+/// assertion doesn't leave it behind.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    pub fn new(name: &str) -> TempDir {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+        let path = env::temp_dir().join(format!(
+            "aoc-{}-{}-{}",
+            name,
+            process::id(),
+            COUNT.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        TempDir(path)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+
+    pub fn join(&self, path: impl AsRef<Path>) -> PathBuf {
+        self.0.join(path)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
@@ -0,0 +1,229 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::Result;
+use itertools::{EitherOrBoth, Itertools};
+
+use crate::answer::Answer;
+use crate::examples::{self, Example};
+
+/// The modification time and length of each watched file, or `None` for a file that
+/// doesn't exist (yet).  Comparing two snapshots taken a little apart is how `aoc
+/// watch` notices edits without an OS-specific notification API.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Snapshot(BTreeMap<PathBuf, Option<(SystemTime, u64)>>);
+
+impl Snapshot {
+    pub fn take(paths: impl IntoIterator<Item = PathBuf>) -> Snapshot {
+        Snapshot(
+            paths
+                .into_iter()
+                .map(|path| {
+                    let state = fs::metadata(&path)
+                        .and_then(|metadata| Ok((metadata.modified()?, metadata.len())))
+                        .ok();
+                    (path, state)
+                })
+                .collect(),
+        )
+    }
+
+    /// The files that were created, changed or deleted since `earlier`.
+    pub fn changed_since(&self, earlier: &Snapshot) -> Vec<PathBuf> {
+        self.0
+            .iter()
+            .merge_join_by(&earlier.0, |(a, _), (b, _)| a.cmp(b))
+            .filter_map(|entry| match entry {
+                EitherOrBoth::Both((path, now), (_, then)) => (now != then).then_some(path),
+                EitherOrBoth::Left((path, now)) => now.is_some().then_some(path),
+                EitherOrBoth::Right((path, then)) => then.is_some().then_some(path),
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+/// Every `.rs` file under `dir`, so that a watcher can tell when to rebuild.
+pub fn source_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for path in fs::read_dir(&dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+        {
+            if path.is_dir() {
+                dirs.push(path);
+            } else if path.extension().is_some_and(|extension| extension == "rs") {
+                files.push(path);
+            }
+        }
+    }
+    files
+}
+
+/// The example inputs and answers for one day in `dir`, whether or not they are named
+/// properly.
+pub fn day_files(dir: &Path, year: usize, day: usize) -> Vec<PathBuf> {
+    let prefix = format!("{}-{:02}-", year, day);
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(&prefix))
+        .map(|entry| entry.path())
+        .collect()
+}
+
+/// The examples for one day in `dir`.
+pub fn day_examples(dir: &Path, year: usize, day: usize) -> Result<Vec<Example>> {
+    Ok(examples::discover(dir)?
+        .into_iter()
+        .filter(|example| example.year == year && example.day == day)
+        .collect())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Pass,
+    /// The answer differed from the expected one, or there was none.
+    Fail {
+        expected: Answer,
+        actual: Option<Answer>,
+    },
+    /// There is no expected answer to compare against yet.
+    NoExpectedAnswer,
+    Error(String),
+}
+
+pub struct ExampleCheck {
+    pub example: Example,
+    pub outcome: Outcome,
+}
+
+impl ExampleCheck {
+    pub fn run(example: Example) -> ExampleCheck {
+        let outcome = match example.expected() {
+            Ok(None) => Outcome::NoExpectedAnswer,
+            Ok(Some(expected)) => match example.run() {
+                Ok(actual) if actual.as_ref() == Some(&expected) => Outcome::Pass,
+                Ok(actual) => Outcome::Fail { expected, actual },
+                Err(err) => Outcome::Error(format!("{:#}", err)),
+            },
+            Err(err) => Outcome::Error(format!("{:#}", err)),
+        };
+        ExampleCheck { example, outcome }
+    }
+}
+
+impl fmt::Display for ExampleCheck {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let label = self.example.label();
+        match &self.outcome {
+            Outcome::Pass => write!(f, "PASS {}", label),
+            Outcome::NoExpectedAnswer => write!(f, "SKIP {}: no expected answer", label),
+            Outcome::Error(err) => write!(f, "FAIL {}: {}", label, err),
+            Outcome::Fail {
+                expected,
+                actual: None,
+            } => write!(f, "FAIL {}: expected {}, got no answer", label, expected),
+            Outcome::Fail {
+                expected,
+                actual: Some(actual),
+            } if !expected.is_multiline() && !actual.is_multiline() => {
+                write!(f, "FAIL {}: expected {}, got {}", label, expected, actual)
+            }
+            Outcome::Fail {
+                expected,
+                actual: Some(actual),
+            } => write!(
+                f,
+                "FAIL {}:\n{}",
+                label,
+                diff(&expected.to_string(), &actual.to_string())
+            ),
+        }
+    }
+}
+
+/// A line-by-line comparison of two multiline answers: matching lines are indented,
+/// differing ones are shown as `-` expected and `+` actual.
+pub fn diff(expected: &str, actual: &str) -> String {
+    expected
+        .lines()
+        .zip_longest(actual.lines())
+        .flat_map(|lines| match lines {
+            EitherOrBoth::Both(e, a) if e == a => vec![format!("  {}", e)],
+            EitherOrBoth::Both(e, a) => vec![format!("- {}", e), format!("+ {}", a)],
+            EitherOrBoth::Left(e) => vec![format!("- {}", e)],
+            EitherOrBoth::Right(a) => vec![format!("+ {}", a)],
+        })
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{TempDir, DEPTHS};
+
+    #[test]
+    fn check_snapshot_changes() {
+        let dir = TempDir::new("watch");
+        let (kept, added) = (dir.join("kept.txt"), dir.join("added.txt"));
+        fs::write(&kept, "1").unwrap();
+        let paths = || vec![kept.clone(), added.clone()];
+
+        let before = Snapshot::take(paths());
+        assert!(Snapshot::take(paths()).changed_since(&before).is_empty());
+
+        fs::write(&added, "2").unwrap();
+        fs::write(&kept, "longer").unwrap();
+        let after = Snapshot::take(paths());
+        assert_eq!(
+            after.changed_since(&before),
+            vec![added.clone(), kept.clone()]
+        );
+
+        fs::remove_file(&added).unwrap();
+        let removed = Snapshot::take(vec![kept.clone()]);
+        assert_eq!(removed.changed_since(&after), vec![added]);
+    }
+
+    #[test]
+    fn check_example_checks() {
+        let dir = TempDir::new("watch-examples");
+        fs::write(dir.join("2021-01-1.txt"), DEPTHS).unwrap();
+        fs::write(dir.join("2021-01-1.answer"), "7\n").unwrap();
+        fs::write(dir.join("2021-01-2.txt"), DEPTHS).unwrap();
+        fs::write(dir.join("2021-01-2.answer"), "6\n").unwrap();
+        fs::write(dir.join("2021-01-2-unanswered.txt"), DEPTHS).unwrap();
+        fs::write(dir.join("2021-02-1.txt"), "forward 1\n").unwrap();
+
+        let checks = day_examples(dir.path(), 2021, 1)
+            .unwrap()
+            .into_iter()
+            .map(|example| ExampleCheck::run(example).to_string())
+            .collect_vec();
+        assert_eq!(
+            checks,
+            vec![
+                "PASS 2021-01-1",
+                "FAIL 2021-01-2: expected 6, got 5",
+                "SKIP 2021-01-2-unanswered: no expected answer",
+            ]
+        );
+    }
+
+    #[test]
+    fn check_diff() {
+        assert_eq!(
+            diff("#..#\n.##.\n#..#", "#..#\n.#..\n#..#\n...."),
+            "  #..#\n- .##.\n+ .#..\n  #..#\n+ ...."
+        );
+    }
+}
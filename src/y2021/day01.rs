@@ -0,0 +1,39 @@
+use anyhow::Result;
+use itertools::Itertools;
+
+use crate::answer::Answer;
+use crate::input::Input;
+use crate::parse_input;
+use crate::solution::Solution;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Parsed = Vec<usize>;
+
+    fn parse(input: &Input) -> Result<Vec<usize>> {
+        parse_input(input.text())
+    }
+
+    fn part1(depths: &Vec<usize>) -> Result<Option<Answer>> {
+        Ok(Some(Answer::from(
+            depths
+                .iter()
+                .tuple_windows()
+                .filter(|(first, second)| second > first)
+                .count(),
+        )))
+    }
+
+    fn part2(depths: &Vec<usize>) -> Result<Option<Answer>> {
+        Ok(Some(Answer::from(
+            depths
+                .iter()
+                .tuple_windows()
+                .map(|(a, b, c)| a + b + c)
+                .tuple_windows()
+                .filter(|tuple: &(usize, usize)| tuple.1 > tuple.0)
+                .count(),
+        )))
+    }
+}
@@ -0,0 +1,28 @@
+use anyhow::Result;
+
+use crate::answer::Answer;
+use crate::input::Input;
+use crate::old::command::Command;
+use crate::old::position::Position;
+use crate::parse_lines;
+use crate::solution::{Progress, Solution};
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Parsed = Vec<Command>;
+    const PROGRESS: [Progress; 2] = [Progress::NoLongerAvailable, Progress::Solved];
+
+    fn parse(input: &Input) -> Result<Vec<Command>> {
+        parse_lines(input.text(), Command::from_str)
+    }
+
+    fn part2(commands: &Vec<Command>) -> Result<Option<Answer>> {
+        Ok(Some(Answer::from(
+            commands
+                .iter()
+                .fold(Position::at_zero(), |pos, &com| pos.exec_command(com))
+                .multiply_x_by_depth(),
+        )))
+    }
+}
@@ -0,0 +1,29 @@
+use anyhow::Result;
+
+use crate::answer::Answer;
+use crate::binary_from_input;
+use crate::input::Input;
+use crate::old::bitaccumulator::DiagsReport;
+use crate::solution::Solution;
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Parsed = DiagsReport;
+
+    fn parse(input: &Input) -> Result<DiagsReport> {
+        Ok(DiagsReport::new(12, binary_from_input(input.text())?))
+    }
+
+    fn part1(report: &DiagsReport) -> Result<Option<Answer>> {
+        Ok(Some(Answer::from(
+            report.gamma_rate() * report.epsilon_rate(),
+        )))
+    }
+
+    fn part2(report: &DiagsReport) -> Result<Option<Answer>> {
+        Ok(Some(Answer::from(
+            report.oxygen_rate() * report.co2_scrub_rate(),
+        )))
+    }
+}
@@ -0,0 +1,34 @@
+use anyhow::{anyhow, Result};
+
+use crate::answer::Answer;
+use crate::input::Input;
+use crate::old::bingo::BingoState;
+use crate::solution::Solution;
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Parsed = BingoState;
+
+    fn parse(input: &Input) -> Result<BingoState> {
+        BingoState::from_sections(input.sections())
+    }
+
+    fn part1(state: &BingoState) -> Result<Option<Answer>> {
+        let score = state
+            .clone()
+            .run_until(|b| b.any_complete())
+            .multiply_complete_sum_unmarked_by_last_number()
+            .ok_or(anyhow!("No board completed"))?;
+        Ok(Some(Answer::from(score)))
+    }
+
+    fn part2(state: &BingoState) -> Result<Option<Answer>> {
+        let score = state
+            .clone()
+            .run_until(|b| b.all_complete())
+            .multiply_complete_sum_unmarked_by_last_number()
+            .ok_or(anyhow!("Not every board completed"))?;
+        Ok(Some(Answer::from(score)))
+    }
+}
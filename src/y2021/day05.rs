@@ -0,0 +1,36 @@
+use anyhow::Result;
+
+use crate::answer::Answer;
+use crate::input::Input;
+use crate::old::coordinates::{GridCounter, LineSegment};
+use crate::parse_lines;
+use crate::solution::Solution;
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Parsed = Vec<LineSegment>;
+
+    fn parse(input: &Input) -> Result<Vec<LineSegment>> {
+        parse_lines(input.text(), LineSegment::from_str)
+    }
+
+    fn part1(segments: &Vec<LineSegment>) -> Result<Option<Answer>> {
+        Ok(Some(Answer::from(count_overlaps(
+            segments.iter().filter(|ls| ls.is_horiz() || ls.is_vert()),
+        ))))
+    }
+
+    fn part2(segments: &Vec<LineSegment>) -> Result<Option<Answer>> {
+        Ok(Some(Answer::from(count_overlaps(segments.iter()))))
+    }
+}
+
+fn count_overlaps<'a>(segments: impl Iterator<Item = &'a LineSegment>) -> usize {
+    segments
+        .flat_map(|ls| ls.coords())
+        .fold(GridCounter::new(), |gc, coords| gc.add_coords(&coords))
+        .into_values()
+        .filter(|&v| v > 1)
+        .count()
+}
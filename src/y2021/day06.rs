@@ -0,0 +1,27 @@
+use anyhow::Result;
+
+use crate::answer::Answer;
+use crate::input::Input;
+use crate::old::lanternfish::LanternShoal;
+use crate::single_line_from_input;
+use crate::solution::Solution;
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Parsed = LanternShoal;
+
+    fn parse(input: &Input) -> Result<LanternShoal> {
+        LanternShoal::from_str(single_line_from_input(input)?)
+    }
+
+    fn part1(shoal: &LanternShoal) -> Result<Option<Answer>> {
+        Ok(Some(Answer::from(shoal.clone().proceed_n_days(80).count())))
+    }
+
+    fn part2(shoal: &LanternShoal) -> Result<Option<Answer>> {
+        Ok(Some(Answer::from(
+            shoal.clone().proceed_n_days(256).count(),
+        )))
+    }
+}
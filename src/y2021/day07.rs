@@ -0,0 +1,33 @@
+use anyhow::{Context, Result};
+use itertools::Itertools;
+
+use crate::answer::Answer;
+use crate::input::Input;
+use crate::old::crabs;
+use crate::single_line_from_input;
+use crate::solution::{Progress, Solution};
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Parsed = Vec<usize>;
+    const PROGRESS: [Progress; 2] = [Progress::NoLongerAvailable, Progress::Solved];
+
+    fn parse(input: &Input) -> Result<Vec<usize>> {
+        single_line_from_input(input)?
+            .split(',')
+            .map(|s| {
+                s.trim()
+                    .parse::<usize>()
+                    .with_context(|| format!("Invalid crab position {:?}", s))
+            })
+            .collect()
+    }
+
+    fn part2(inputs: &Vec<usize>) -> Result<Option<Answer>> {
+        let min_cost = (0..).fold_while(usize::MAX, |old_cost, new_position| {
+            crabs::fold_step(inputs, old_cost, new_position)
+        });
+        Ok(Some(Answer::from(min_cost.into_inner())))
+    }
+}
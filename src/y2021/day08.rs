@@ -0,0 +1,55 @@
+use anyhow::{anyhow, Result};
+use itertools::Itertools;
+
+use crate::answer::Answer;
+use crate::input::Input;
+use crate::old::segment_display::{SegmentDisplay, SegmentMapping};
+use crate::parse_lines;
+use crate::solution::Solution;
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    /// The four decoded output digits of each display.
+    type Parsed = Vec<Vec<usize>>;
+
+    fn parse(input: &Input) -> Result<Vec<Vec<usize>>> {
+        parse_lines(input.text(), decode_display)
+    }
+
+    fn part1(outputs: &Vec<Vec<usize>>) -> Result<Option<Answer>> {
+        Ok(Some(Answer::from(
+            outputs
+                .iter()
+                .flatten()
+                .filter(|&&digit| matches!(digit, 1 | 4 | 7 | 8))
+                .count(),
+        )))
+    }
+
+    fn part2(outputs: &Vec<Vec<usize>>) -> Result<Option<Answer>> {
+        Ok(Some(Answer::from(
+            outputs
+                .iter()
+                .map(|digits| digits.iter().fold(0, |value, digit| value * 10 + digit))
+                .sum::<usize>(),
+        )))
+    }
+}
+
+fn decode_display(display: &str) -> Result<Vec<usize>> {
+    let (input_segs, output_segs) = display
+        .split(" | ")
+        .map(|segments| segments.split(' ').map(|s| s.to_string()).collect_vec())
+        .collect_tuple::<(_, _)>()
+        .ok_or(anyhow!("Expected \"<patterns> | <outputs>\""))?;
+    let mapping = SegmentMapping::find_valid(&input_segs).ok_or(anyhow!("No valid mapping"))?;
+    output_segs
+        .iter()
+        .map(|seg| {
+            SegmentDisplay::from_str_with_mapping(seg, &mapping)
+                .to_int()
+                .ok_or_else(|| anyhow!("Can't decode output {:?}", seg))
+        })
+        .collect()
+}
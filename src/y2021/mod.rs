@@ -0,0 +1,22 @@
+use crate::solution::Registration;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+
+pub const SOLUTIONS: &[Registration] = &[
+    Registration::new::<day01::Day01>(2021, 1),
+    Registration::new::<day02::Day02>(2021, 2),
+    Registration::new::<day03::Day03>(2021, 3),
+    Registration::new::<day04::Day04>(2021, 4),
+    Registration::new::<day05::Day05>(2021, 5),
+    Registration::new::<day06::Day06>(2021, 6),
+    Registration::new::<day07::Day07>(2021, 7),
+    Registration::new::<day08::Day08>(2021, 8),
+    // Add next day above this line.
+];
@@ -0,0 +1,44 @@
+use anyhow::{anyhow, Result};
+
+use crate::answer::Answer;
+use crate::input::Input;
+use crate::parse_input;
+use crate::solution::Solution;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    /// The total calories carried by each elf, largest first.
+    type Parsed = Vec<usize>;
+
+    fn parse(input: &Input) -> Result<Vec<usize>> {
+        let mut totals = input
+            .sections()
+            .map(|elf| Ok(parse_input::<usize>(elf)?.iter().sum()))
+            .collect::<Result<Vec<usize>>>()?;
+        totals.sort_unstable_by(|a, b| b.cmp(a));
+        Ok(totals)
+    }
+
+    fn part1(totals: &Vec<usize>) -> Result<Option<Answer>> {
+        let max = totals.first().ok_or(anyhow!("No elves in input"))?;
+        Ok(Some(Answer::from(*max)))
+    }
+
+    fn part2(totals: &Vec<usize>) -> Result<Option<Answer>> {
+        Ok(Some(Answer::from(totals.iter().take(3).sum::<usize>())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_top_three_with_uneven_elves() {
+        let input = Input::from("100\n\n200\n\n50\n50\n50\n50\n50\n50");
+        let totals = Day01::parse(&input).unwrap();
+        assert_eq!(Day01::part1(&totals).unwrap(), Some(Answer::from(300usize)));
+        assert_eq!(Day01::part2(&totals).unwrap(), Some(Answer::from(600usize)));
+    }
+}
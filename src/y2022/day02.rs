@@ -0,0 +1,22 @@
+use anyhow::Result;
+
+use crate::answer::Answer;
+use crate::input::Input;
+use crate::old::rockpaperscissors::score_guide_round;
+use crate::parse_lines;
+use crate::solution::{Progress, Solution};
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Parsed = Vec<usize>;
+    const PROGRESS: [Progress; 2] = [Progress::NoLongerAvailable, Progress::Solved];
+
+    fn parse(input: &Input) -> Result<Vec<usize>> {
+        parse_lines(input.text(), score_guide_round)
+    }
+
+    fn part2(scores: &Vec<usize>) -> Result<Option<Answer>> {
+        Ok(Some(Answer::from(scores.iter().sum::<usize>())))
+    }
+}
@@ -0,0 +1,15 @@
+use anyhow::Result;
+
+use crate::input::Input;
+use crate::solution::{Progress, Solution};
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Parsed = ();
+    const PROGRESS: [Progress; 2] = [Progress::Stubbed, Progress::Stubbed];
+
+    fn parse(_input: &Input) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,15 @@
+use anyhow::Result;
+
+use crate::input::Input;
+use crate::solution::{Progress, Solution};
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Parsed = ();
+    const PROGRESS: [Progress; 2] = [Progress::Stubbed, Progress::Stubbed];
+
+    fn parse(_input: &Input) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,14 @@
+use crate::solution::Registration;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+
+pub const SOLUTIONS: &[Registration] = &[
+    Registration::new::<day01::Day01>(2022, 1),
+    Registration::new::<day02::Day02>(2022, 2),
+    Registration::new::<day03::Day03>(2022, 3),
+    Registration::new::<day04::Day04>(2022, 4),
+    // Add next day above this line.
+];
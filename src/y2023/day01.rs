@@ -0,0 +1,84 @@
+use std::convert::TryInto;
+
+use anyhow::{anyhow, Context, Result};
+use regex::Regex;
+
+use crate::answer::Answer;
+use crate::input::Input;
+use crate::solution::Solution;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Parsed = Vec<String>;
+
+    fn parse(input: &Input) -> Result<Vec<String>> {
+        Ok(input.lines().map(|s| s.to_string()).collect())
+    }
+
+    fn part1(lines: &Vec<String>) -> Result<Option<Answer>> {
+        Ok(Some(Answer::from(sum_calibration_values(
+            lines,
+            fix_calibration_line,
+        )?)))
+    }
+
+    fn part2(lines: &Vec<String>) -> Result<Option<Answer>> {
+        Ok(Some(Answer::from(sum_calibration_values(
+            lines,
+            fix_calibration_line_with_string_digits,
+        )?)))
+    }
+}
+
+fn sum_calibration_values(lines: &[String], fix_line: fn(&str) -> Result<usize>) -> Result<usize> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, s)| fix_line(s).with_context(|| format!("Line {}: {:?}", i + 1, s)))
+        .sum()
+}
+
+fn fix_calibration_line(s: &str) -> Result<usize> {
+    let mut iterator = s.chars().filter_map(|c| c.to_digit(10)).peekable();
+    let first_digit = *iterator.peek().ok_or(anyhow!("No first digit"))?;
+    let last_digit = iterator.last().ok_or(anyhow!("No last digit"))?;
+    Ok((first_digit * 10 + last_digit).try_into()?)
+}
+
+fn fix_calibration_line_with_string_digits(s: &str) -> Result<usize> {
+    let first_digit_regex = Regex::new("one|two|three|four|five|six|seven|eight|nine|[0-9]")?;
+    let last_digit_regex =
+        Regex::new("(?:.*)(one|two|three|four|five|six|seven|eight|nine|[0-9])")?;
+    let first_digit = to_digit_incl_text(
+        first_digit_regex
+            .find(s)
+            .ok_or(anyhow!("No first digit"))?
+            .as_str(),
+    )?;
+    let last_digit = to_digit_incl_text(
+        last_digit_regex
+            .captures(s)
+            .ok_or(anyhow!("No last digit"))?
+            .get(1)
+            .ok_or(anyhow!("No last digit"))?
+            .as_str(),
+    )?;
+    Ok(first_digit * 10 + last_digit)
+}
+
+fn to_digit_incl_text(s: &str) -> Result<usize> {
+    match s {
+        "0" => Ok(0),
+        "one" | "1" => Ok(1),
+        "two" | "2" => Ok(2),
+        "three" | "3" => Ok(3),
+        "four" | "4" => Ok(4),
+        "five" | "5" => Ok(5),
+        "six" | "6" => Ok(6),
+        "seven" | "7" => Ok(7),
+        "eight" | "8" => Ok(8),
+        "nine" | "9" => Ok(9),
+        _ => Err(anyhow!("Invalid digit")),
+    }
+}
@@ -0,0 +1,8 @@
+use crate::solution::Registration;
+
+pub mod day01;
+
+pub const SOLUTIONS: &[Registration] = &[
+    Registration::new::<day01::Day01>(2023, 1),
+    // Add next day above this line.
+];
@@ -0,0 +1,45 @@
+//! One test per example file under inputs/examples, run through the registry.  Examples
+//! without an expected answer yet are ignored rather than failed.
+
+use aoc::examples::{self, examples_dir};
+use libtest_mimic::{Arguments, Failed, Trial};
+
+fn main() {
+    let args = Arguments::from_args();
+    let examples = examples::discover(&examples_dir()).unwrap();
+
+    let trials = examples
+        .into_iter()
+        .map(|example| {
+            let expected = example.expected().unwrap();
+            let unanswered = expected.is_none();
+            Trial::test(example.label(), move || {
+                let expected = match expected {
+                    Some(expected) => expected,
+                    None => {
+                        println!(
+                            "Skipping: no expected answer in {}",
+                            example.answer_path().display()
+                        );
+                        return Ok(());
+                    }
+                };
+                match example.run().map_err(|err| format!("{:?}", err))? {
+                    Some(answer) if answer == expected => Ok(()),
+                    Some(answer) => Err(Failed::from(format!(
+                        "expected {}, got {}",
+                        expected, answer
+                    ))),
+                    None => Err(Failed::from(format!(
+                        "expected {}, got no answer",
+                        expected
+                    ))),
+                }
+            })
+            .with_kind(if unanswered { "no answer" } else { "" })
+            .with_ignored_flag(unanswered)
+        })
+        .collect();
+
+    libtest_mimic::run(&args, trials).exit();
+}
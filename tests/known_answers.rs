@@ -0,0 +1,44 @@
+//! One test per entry in answers.toml, run against the real puzzle inputs.  Entries
+//! whose input file hasn't been downloaded are ignored rather than failed.
+
+use aoc::cargo_input_file_path;
+use aoc::known_answers::{self, answers_file_path, Verdict};
+use libtest_mimic::{Arguments, Failed, Trial};
+
+fn main() {
+    let args = Arguments::from_args();
+    let known = known_answers::load(&answers_file_path()).unwrap();
+
+    let trials = known
+        .into_iter()
+        .map(|known| {
+            let name = format!("{}-{:02}-{}", known.year, known.day, known.part);
+            let input_path = cargo_input_file_path(known.year, known.day);
+            let missing = !input_path.is_file();
+            Trial::test(name, move || {
+                let verification =
+                    known_answers::verify(std::slice::from_ref(&known), cargo_input_file_path)
+                        .remove(0);
+                match verification.verdict {
+                    Verdict::Correct => Ok(()),
+                    Verdict::MissingInput => {
+                        println!(
+                            "Skipping: input file {} does not exist",
+                            input_path.display()
+                        );
+                        Ok(())
+                    }
+                    Verdict::Wrong(answer) => Err(Failed::from(format!(
+                        "expected {}, got {}",
+                        known.answer, answer
+                    ))),
+                    verdict => Err(Failed::from(verdict.to_string())),
+                }
+            })
+            .with_kind(if missing { "missing input" } else { "" })
+            .with_ignored_flag(missing)
+        })
+        .collect();
+
+    libtest_mimic::run(&args, trials).exit();
+}